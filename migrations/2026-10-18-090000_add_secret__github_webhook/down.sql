@@ -0,0 +1 @@
+alter table github_webhook drop column secret;
@@ -0,0 +1,2 @@
+alter table github_webhook add column secret varchar(64) not null default '';
+alter table github_webhook alter column secret drop default;
@@ -0,0 +1,15 @@
+create table webhook_delivery (
+    id serial primary key,
+    delivery_id varchar(64) not null,
+    event varchar(64) not null,
+    action varchar(64),
+    repository_owner varchar(100),
+    repository_name varchar(100),
+    received_at timestamp with time zone not null default now(),
+    status varchar(16) not null,
+    error text,
+    constraint uq_webhook_delivery unique (delivery_id)
+);
+
+create index ix_webhook_delivery__repository
+    on webhook_delivery (repository_owner, repository_name, received_at);
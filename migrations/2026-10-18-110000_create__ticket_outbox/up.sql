@@ -0,0 +1,14 @@
+create table ticket_outbox (
+    id serial primary key,
+    board_repository_id int not null references board_repository(id) on delete cascade,
+    board_id bigint not null,
+    title text not null,
+    body text not null,
+    status varchar(16) not null,
+    attempts int not null default 0,
+    next_attempt_at timestamp with time zone not null default now(),
+    last_error text,
+    created_at timestamp with time zone not null default now()
+);
+
+create index ix_ticket_outbox__status_next_attempt_at on ticket_outbox (status, next_attempt_at);
@@ -0,0 +1,4 @@
+alter table ticket_outbox drop column issue_number;
+alter table ticket_outbox drop column issue_id;
+
+drop table issue_ticket;
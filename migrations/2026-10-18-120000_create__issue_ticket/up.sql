@@ -0,0 +1,11 @@
+create table issue_ticket (
+    id serial primary key,
+    board_repository_id int not null references board_repository(id) on delete cascade,
+    issue_id bigint not null,
+    issue_number int not null,
+    ticket_id bigint not null,
+    constraint uq_issue_ticket unique (board_repository_id, issue_id)
+);
+
+alter table ticket_outbox add column issue_id bigint;
+alter table ticket_outbox add column issue_number int;
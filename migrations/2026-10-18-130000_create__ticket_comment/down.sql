@@ -0,0 +1 @@
+drop table ticket_comment;
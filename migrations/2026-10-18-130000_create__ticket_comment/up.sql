@@ -0,0 +1,7 @@
+create table ticket_comment (
+    id serial primary key,
+    issue_ticket_id int not null references issue_ticket(id) on delete cascade,
+    comment_id bigint not null,
+    ticket_comment_id bigint not null,
+    constraint uq_ticket_comment unique (issue_ticket_id, comment_id)
+);
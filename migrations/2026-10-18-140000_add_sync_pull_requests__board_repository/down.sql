@@ -0,0 +1 @@
+alter table board_repository drop column sync_pull_requests;
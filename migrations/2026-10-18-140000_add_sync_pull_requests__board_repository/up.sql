@@ -0,0 +1 @@
+alter table board_repository add column sync_pull_requests boolean not null default false;
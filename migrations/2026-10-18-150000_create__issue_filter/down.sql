@@ -0,0 +1 @@
+drop table issue_filter;
@@ -0,0 +1,8 @@
+create table issue_filter (
+    id serial primary key,
+    board_repository_id int not null references board_repository(id) on delete cascade,
+    kind varchar(32) not null,
+    value varchar(256) not null
+);
+
+create index ix_issue_filter__board_repository_id on issue_filter (board_repository_id);
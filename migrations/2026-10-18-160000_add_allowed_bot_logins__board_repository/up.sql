@@ -0,0 +1 @@
+alter table board_repository add column allowed_bot_logins text[] not null default '{}';
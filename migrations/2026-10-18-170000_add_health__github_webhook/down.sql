@@ -0,0 +1,3 @@
+alter table github_webhook drop column last_error;
+alter table github_webhook drop column last_delivery_at;
+alter table github_webhook drop column last_ping_at;
@@ -0,0 +1,3 @@
+alter table github_webhook add column last_ping_at timestamptz;
+alter table github_webhook add column last_delivery_at timestamptz;
+alter table github_webhook add column last_error text;
@@ -0,0 +1,3 @@
+drop index ix_board_repository__repository_id;
+alter table board_repository drop column is_read_only;
+alter table board_repository drop column repository_id;
@@ -0,0 +1,3 @@
+alter table board_repository add column repository_id bigint;
+alter table board_repository add column is_read_only boolean not null default false;
+create index ix_board_repository__repository_id on board_repository (repository_id);
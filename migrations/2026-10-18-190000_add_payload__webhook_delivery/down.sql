@@ -0,0 +1,2 @@
+alter table webhook_delivery drop column headers;
+alter table webhook_delivery drop column payload;
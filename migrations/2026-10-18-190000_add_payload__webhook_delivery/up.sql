@@ -0,0 +1,2 @@
+alter table webhook_delivery add column payload text;
+alter table webhook_delivery add column headers text;
@@ -0,0 +1 @@
+drop table column_state;
@@ -0,0 +1,7 @@
+create table column_state (
+    id serial primary key,
+    board_repository_id int not null references board_repository(id) on delete cascade,
+    column_id bigint not null,
+    state varchar(16) not null,
+    constraint uq_column_state unique (board_repository_id, column_id)
+);
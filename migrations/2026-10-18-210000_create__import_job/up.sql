@@ -0,0 +1,13 @@
+create table import_job (
+    id serial primary key,
+    board_repository_id int not null references board_repository(id) on delete cascade,
+    status varchar(16) not null,
+    total int not null default 0,
+    imported int not null default 0,
+    skipped int not null default 0,
+    error text,
+    created_at timestamp with time zone not null default now(),
+    finished_at timestamp with time zone
+);
+
+create index ix_import_job__status on import_job (status, created_at);
@@ -0,0 +1 @@
+alter table board_repository add column events text[] not null default '{issues,issue_comment,pull_request,push}';
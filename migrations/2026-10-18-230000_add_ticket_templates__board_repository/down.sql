@@ -0,0 +1,2 @@
+alter table board_repository drop column ticket_body_template;
+alter table board_repository drop column ticket_title_template;
@@ -0,0 +1,2 @@
+alter table board_repository add column ticket_title_template text;
+alter table board_repository add column ticket_body_template text;
@@ -0,0 +1 @@
+alter table github_profile drop column github_api_url;
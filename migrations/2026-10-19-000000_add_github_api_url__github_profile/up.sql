@@ -0,0 +1 @@
+alter table github_profile add column github_api_url text;
@@ -0,0 +1 @@
+alter table import_job add column updated_at timestamp with time zone not null default now();
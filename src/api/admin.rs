@@ -0,0 +1,197 @@
+use actix_web::{web, HttpResponse, HttpRequest};
+
+use crate::entities::admin::Admin;
+use crate::errors::GITrelloError;
+use crate::models::ticket_outbox::{TicketOutboxItem, STATUS_DEAD};
+use crate::models::webhook_delivery::WebhookDelivery;
+use crate::services::github_webhook_service::GithubWebhookProcessingService;
+use crate::services::ticket_outbox_service::TicketOutboxService;
+use crate::services::webhook_delivery_service::WebhookDeliveryService;
+use crate::services::webhook_reconciliation_service::WebhookReconciliationService;
+use crate::state::State;
+use crate::value_objects::request_data::admin::{
+    GetTicketOutboxQueryParams, ReconcileWebhooksQueryParams, ReplayWebhookDeliveriesRequest,
+};
+use crate::value_objects::response_data::ticket_outbox::TicketOutboxItemResponse;
+use crate::value_objects::response_data::webhook_delivery::WebhookDeliveryReplayResponse;
+use crate::value_objects::response_data::webhook_reconciliation::WebhookReconciliationResponse;
+
+const STATUS_SKIPPED: &str = "skipped";
+
+fn to_ticket_outbox_item_response(ticket_outbox_item: TicketOutboxItem) -> TicketOutboxItemResponse {
+    TicketOutboxItemResponse {
+        id: ticket_outbox_item.id,
+        board_repository_id: ticket_outbox_item.board_repository_id,
+        board_id: ticket_outbox_item.board_id.to_string(),
+        title: ticket_outbox_item.title,
+        status: ticket_outbox_item.status,
+        attempts: ticket_outbox_item.attempts,
+        next_attempt_at: ticket_outbox_item.next_attempt_at,
+        last_error: ticket_outbox_item.last_error,
+        created_at: ticket_outbox_item.created_at,
+    }
+}
+
+fn to_webhook_delivery_replay_response(webhook_delivery: WebhookDelivery) -> WebhookDeliveryReplayResponse {
+    WebhookDeliveryReplayResponse {
+        id: webhook_delivery.id,
+        delivery_id: webhook_delivery.delivery_id,
+        event: webhook_delivery.event,
+        status: webhook_delivery.status,
+        error: webhook_delivery.error,
+    }
+}
+
+#[get("/api/v1/admin/ticket-outbox")]
+pub async fn get_ticket_outbox(
+    req: HttpRequest,
+    web::Query(query_params): web::Query<GetTicketOutboxQueryParams>,
+    state: web::Data<State>,
+) -> Result<HttpResponse, GITrelloError>
+{
+    let admin = Admin::from_request_headers(req.headers());
+    if !admin.is_authenticated(state.admin_token.as_str()) {
+        return Err(GITrelloError::NotAuthenticated)
+    }
+
+    let status = query_params.status.unwrap_or_else(|| String::from(STATUS_DEAD));
+
+    let ticket_outbox_service = TicketOutboxService::new(&state)?;
+    let ticket_outbox_items = ticket_outbox_service.get_by_status(status.as_str()).await?;
+
+    let response_data: Vec<TicketOutboxItemResponse> = ticket_outbox_items
+        .into_iter()
+        .map(to_ticket_outbox_item_response)
+        .collect();
+
+    Ok(HttpResponse::Ok().json(response_data))
+}
+
+#[post("/api/v1/admin/ticket-outbox/{id}/retry")]
+pub async fn retry_ticket_outbox_item(
+    req: HttpRequest,
+    web::Path((id, )): web::Path<(i32, )>,
+    state: web::Data<State>,
+) -> Result<HttpResponse, GITrelloError>
+{
+    let admin = Admin::from_request_headers(req.headers());
+    if !admin.is_authenticated(state.admin_token.as_str()) {
+        return Err(GITrelloError::NotAuthenticated)
+    }
+
+    let ticket_outbox_service = TicketOutboxService::new(&state)?;
+    let ticket_outbox_item = ticket_outbox_service.requeue(id).await?;
+
+    Ok(HttpResponse::Ok().json(to_ticket_outbox_item_response(ticket_outbox_item)))
+}
+
+#[post("/api/v1/admin/webhook-deliveries/{id}/replay")]
+pub async fn replay_webhook_delivery(
+    req: HttpRequest,
+    web::Path((id, )): web::Path<(i32, )>,
+    state: web::Data<State>,
+) -> Result<HttpResponse, GITrelloError>
+{
+    let admin = Admin::from_request_headers(req.headers());
+    if !admin.is_authenticated(state.admin_token.as_str()) {
+        return Err(GITrelloError::NotAuthenticated)
+    }
+
+    let webhook_delivery_service = WebhookDeliveryService::new(&state)?;
+    let webhook_delivery = webhook_delivery_service.get_by_id(id).await?;
+
+    let processing_service = GithubWebhookProcessingService::new(&state);
+    let webhook_delivery = webhook_delivery_service
+        .replay(webhook_delivery, &processing_service)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(to_webhook_delivery_replay_response(webhook_delivery)))
+}
+
+#[post("/api/v1/admin/webhook-deliveries/replay")]
+pub async fn replay_webhook_deliveries(
+    req: HttpRequest,
+    json: web::Json<ReplayWebhookDeliveriesRequest>,
+    state: web::Data<State>,
+) -> Result<HttpResponse, GITrelloError>
+{
+    let admin = Admin::from_request_headers(req.headers());
+    if !admin.is_authenticated(state.admin_token.as_str()) {
+        return Err(GITrelloError::NotAuthenticated)
+    }
+
+    let webhook_delivery_service = WebhookDeliveryService::new(&state)?;
+    let webhook_deliveries = webhook_delivery_service
+        .get_by_repository_and_received_at(
+            json.repository_owner.as_str(),
+            json.repository_name.as_str(),
+            json.from,
+            json.to,
+        )
+        .await?;
+
+    // Deliveries are replayed one by one in the order they were received
+    let processing_service = GithubWebhookProcessingService::new(&state);
+    let mut response_data = Vec::new();
+    for webhook_delivery in webhook_deliveries {
+        let id = webhook_delivery.id;
+        let delivery_id = webhook_delivery.delivery_id.clone();
+        let event = webhook_delivery.event.clone();
+
+        let result = webhook_delivery_service
+            .replay(webhook_delivery, &processing_service)
+            .await;
+
+        response_data.push(
+            match result {
+                Ok(webhook_delivery) => to_webhook_delivery_replay_response(webhook_delivery),
+                Err(e) => WebhookDeliveryReplayResponse {
+                    id,
+                    delivery_id,
+                    event,
+                    status: String::from(STATUS_SKIPPED),
+                    error: Some(e.to_string()),
+                },
+            }
+        );
+    }
+
+    Ok(HttpResponse::Ok().json(response_data))
+}
+
+// Reports only repositories that are out of sync, dry run leaves both GitHub and the database untouched
+#[post("/api/v1/admin/webhook-reconciliations")]
+pub async fn reconcile_webhooks(
+    req: HttpRequest,
+    web::Query(query_params): web::Query<ReconcileWebhooksQueryParams>,
+    state: web::Data<State>,
+) -> Result<HttpResponse, GITrelloError>
+{
+    let admin = Admin::from_request_headers(req.headers());
+    if !admin.is_authenticated(state.admin_token.as_str()) {
+        return Err(GITrelloError::NotAuthenticated)
+    }
+
+    let webhook_reconciliation_service = WebhookReconciliationService::new(&state)?;
+    let reports = webhook_reconciliation_service.reconcile(query_params.dry_run).await?;
+
+    let response_data: Vec<WebhookReconciliationResponse> = reports
+        .into_iter()
+        .filter(|report| report.has_changes())
+        .map(|report| WebhookReconciliationResponse {
+            repository_owner: report.repository_owner,
+            repository_name: report.repository_name,
+            missing_webhook: report.missing_webhook,
+            outdated_events: report.outdated_events,
+            missing_secret: report.missing_secret,
+            mismatched_github_webhook_ids: report.mismatched_github_webhook_ids,
+            orphaned_webhook_ids: report.orphaned_webhook_ids
+                .into_iter()
+                .map(|webhook_id| webhook_id.to_string())
+                .collect(),
+            error: report.error,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(response_data))
+}
@@ -2,13 +2,34 @@ use actix_web::{web, HttpResponse, HttpRequest};
 
 use crate::entities::user::User;
 use crate::services::board_repository_service::BoardRepositoryService;
+use crate::services::import_job_service::ImportJobService;
+use crate::services::ticket_template::{render_ticket, validate_template, TicketTemplateContext};
 use crate::state::State;
+use crate::models::board_repository::{BoardRepository, UpdateBoardRepository};
 use crate::value_objects::request_data::board_repository::{
-    GetBoardRepositoryQueryParams, NewBoardRepositoryRequest,
+    AddBoardRepositoryRequest, GetBoardRepositoryQueryParams, NewBoardRepositoryRequest,
+    TicketTemplatePreviewRequest, UpdateBoardRepositoryRequest,
+};
+use crate::value_objects::response_data::board_repository::{
+    BoardRepositoryResponse, TicketTemplatePreviewResponse,
 };
-use crate::value_objects::response_data::board_repository::BoardRepositoryResponse;
 use crate::errors::GITrelloError;
 
+fn to_response(board_repository: BoardRepository) -> BoardRepositoryResponse {
+    BoardRepositoryResponse {
+        id: board_repository.id,
+        board_id: board_repository.board_id.to_string(),
+        repository_name: board_repository.repository_name,
+        repository_owner: board_repository.repository_owner,
+        sync_pull_requests: board_repository.sync_pull_requests,
+        allowed_bot_logins: board_repository.allowed_bot_logins,
+        is_read_only: board_repository.is_read_only,
+        events: board_repository.events,
+        ticket_title_template: board_repository.ticket_title_template,
+        ticket_body_template: board_repository.ticket_body_template,
+    }
+}
+
 #[put("/api/v1/board-repositories")]
 pub async fn create_or_update_board_repository(
     req: HttpRequest,
@@ -32,12 +53,12 @@ pub async fn create_or_update_board_repository(
 
     return match result {
         Ok(board_repository_upsert_result) => {
-            let response_data = BoardRepositoryResponse {
-                id: board_repository_upsert_result.0.id,
-                board_id: board_repository_upsert_result.0.board_id.to_string(),
-                repository_name: board_repository_upsert_result.0.repository_name,
-                repository_owner: board_repository_upsert_result.0.repository_owner,
-            };
+            if json.import_issues {
+                let import_job_service = ImportJobService::new(&state)?;
+                import_job_service.enqueue(&board_repository_upsert_result.0).await?;
+            }
+
+            let response_data = to_response(board_repository_upsert_result.0);
 
             match board_repository_upsert_result.1 {
                 true => Ok(HttpResponse::Created().json(response_data)),
@@ -48,6 +69,37 @@ pub async fn create_or_update_board_repository(
     }
 }
 
+#[patch("/api/v1/board-repositories/{id}")]
+pub async fn update_board_repository(
+    req: HttpRequest,
+    web::Path((id, )): web::Path<(i32, )>,
+    json: web::Json<UpdateBoardRepositoryRequest>,
+    state: web::Data<State>,
+) -> Result<HttpResponse, GITrelloError>
+{
+    let user = User::from_request_extensions(req.extensions());
+    if !user.is_authenticated() {
+        return Err(GITrelloError::NotAuthenticated)
+    }
+
+    let board_repository_service = BoardRepositoryService::new(&state, &user)?;
+    let board_repository = board_repository_service
+        .update(
+            id,
+            UpdateBoardRepository {
+                sync_pull_requests: json.sync_pull_requests,
+                allowed_bot_logins: json.allowed_bot_logins.clone(),
+                events: json.events.clone(),
+                ticket_title_template: json.ticket_title_template.clone().map(Some),
+                ticket_body_template: json.ticket_body_template.clone().map(Some),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    Ok(HttpResponse::Ok().json(to_response(board_repository)))
+}
+
 #[delete("/api/v1/board-repositories/{id}")]
 pub async fn delete_board_repository(
     req: HttpRequest,
@@ -82,12 +134,112 @@ pub async fn get_board_repository(
         .get_by_board_id(query_params.board_id)
         .await?;
 
-    let response_data = BoardRepositoryResponse {
-        id: board_repository.id,
-        board_id: board_repository.board_id.to_string(),
-        repository_name: board_repository.repository_name,
-        repository_owner: board_repository.repository_owner,
-    };
+    Ok(HttpResponse::Ok().json(to_response(board_repository)))
+}
+
+#[get("/api/v1/boards/{board_id}/repositories")]
+pub async fn get_board_repositories(
+    req: HttpRequest,
+    web::Path((board_id, )): web::Path<(i64, )>,
+    state: web::Data<State>,
+) -> Result<HttpResponse, GITrelloError>
+{
+    let user = User::from_request_extensions(req.extensions());
+    if !user.is_authenticated() {
+        return Err(GITrelloError::NotAuthenticated)
+    }
+
+    let board_repository_service = BoardRepositoryService::new(&state, &user)?;
+    let board_repositories = board_repository_service.get_all_by_board_id(board_id).await?;
+
+    let response_data: Vec<BoardRepositoryResponse> = board_repositories
+        .into_iter()
+        .map(to_response)
+        .collect();
 
     Ok(HttpResponse::Ok().json(response_data))
 }
+
+#[post("/api/v1/boards/{board_id}/repositories")]
+pub async fn add_board_repository(
+    req: HttpRequest,
+    web::Path((board_id, )): web::Path<(i64, )>,
+    json: web::Json<AddBoardRepositoryRequest>,
+    state: web::Data<State>,
+) -> Result<HttpResponse, GITrelloError>
+{
+    let user = User::from_request_extensions(req.extensions());
+    if !user.is_authenticated() {
+        return Err(GITrelloError::NotAuthenticated)
+    }
+
+    let board_repository_service = BoardRepositoryService::new(&state, &user)?;
+    let (board_repository, is_created) = board_repository_service
+        .add(board_id, json.repository_name.as_str(), json.repository_owner.as_str())
+        .await?;
+
+    if json.import_issues {
+        let import_job_service = ImportJobService::new(&state)?;
+        import_job_service.enqueue(&board_repository).await?;
+    }
+
+    match is_created {
+        true => Ok(HttpResponse::Created().json(to_response(board_repository))),
+        _ => Ok(HttpResponse::Ok().json(to_response(board_repository))),
+    }
+}
+
+#[delete("/api/v1/boards/{board_id}/repositories/{id}")]
+pub async fn remove_board_repository(
+    req: HttpRequest,
+    web::Path((board_id, id)): web::Path<(i64, i32)>,
+    state: web::Data<State>,
+) -> Result<HttpResponse, GITrelloError>
+{
+    let user = User::from_request_extensions(req.extensions());
+    if !user.is_authenticated() {
+        return Err(GITrelloError::NotAuthenticated)
+    }
+
+    let board_repository_service = BoardRepositoryService::new(&state, &user)?;
+    board_repository_service.delete_from_board(board_id, id).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+// Renders templates of the board repository, or the ones being edited, against a sample issue
+#[post("/api/v1/board-repositories/{id}/ticket-template-preview")]
+pub async fn preview_ticket_template(
+    req: HttpRequest,
+    web::Path((id, )): web::Path<(i32, )>,
+    json: web::Json<TicketTemplatePreviewRequest>,
+    state: web::Data<State>,
+) -> Result<HttpResponse, GITrelloError>
+{
+    let user = User::from_request_extensions(req.extensions());
+    if !user.is_authenticated() {
+        return Err(GITrelloError::NotAuthenticated)
+    }
+
+    let board_repository_service = BoardRepositoryService::new(&state, &user)?;
+    let mut board_repository = board_repository_service.get_by_id(id).await?;
+
+    let json = json.into_inner();
+    for template in json.ticket_title_template.iter().chain(json.ticket_body_template.iter()) {
+        validate_template(template.as_str())?;
+    }
+    // Same as with updates, empty templates stand for the default ones
+    if let Some(template) = json.ticket_title_template {
+        board_repository.ticket_title_template = Some(template).filter(|template| !template.trim().is_empty());
+    }
+    if let Some(template) = json.ticket_body_template {
+        board_repository.ticket_body_template = Some(template).filter(|template| !template.trim().is_empty());
+    }
+
+    let context = TicketTemplateContext::sample(
+        format!("{}/{}", board_repository.repository_owner, board_repository.repository_name).as_str(),
+    );
+    let (title, body) = render_ticket(&board_repository, &context);
+
+    Ok(HttpResponse::Ok().json(TicketTemplatePreviewResponse { title, body }))
+}
@@ -0,0 +1,69 @@
+use actix_web::{web, HttpResponse, HttpRequest};
+
+use crate::entities::user::User;
+use crate::errors::GITrelloError;
+use crate::models::column_state::ColumnState;
+use crate::services::board_repository_service::BoardRepositoryService;
+use crate::services::column_state_service::ColumnStateService;
+use crate::state::State;
+use crate::value_objects::request_data::column_state::ColumnStateRequest;
+use crate::value_objects::response_data::column_state::ColumnStateResponse;
+
+fn to_response(column_states: Vec<ColumnState>) -> Vec<ColumnStateResponse> {
+    column_states
+        .into_iter()
+        .map(|column_state| ColumnStateResponse {
+            column_id: column_state.column_id.to_string(),
+            state: column_state.state,
+        })
+        .collect()
+}
+
+#[get("/api/v1/board-repositories/{id}/column-states")]
+pub async fn get_column_states(
+    req: HttpRequest,
+    web::Path((id, )): web::Path<(i32, )>,
+    state: web::Data<State>,
+) -> Result<HttpResponse, GITrelloError>
+{
+    let user = User::from_request_extensions(req.extensions());
+    if !user.is_authenticated() {
+        return Err(GITrelloError::NotAuthenticated)
+    }
+
+    let board_repository_service = BoardRepositoryService::new(&state, &user)?;
+    let board_repository = board_repository_service.get_by_id(id).await?;
+
+    let column_state_service = ColumnStateService::new(&state)?;
+    let column_states = column_state_service.get_by_board_repository(&board_repository).await?;
+
+    Ok(HttpResponse::Ok().json(to_response(column_states)))
+}
+
+#[put("/api/v1/board-repositories/{id}/column-states")]
+pub async fn update_column_states(
+    req: HttpRequest,
+    web::Path((id, )): web::Path<(i32, )>,
+    json: web::Json<Vec<ColumnStateRequest>>,
+    state: web::Data<State>,
+) -> Result<HttpResponse, GITrelloError>
+{
+    let user = User::from_request_extensions(req.extensions());
+    if !user.is_authenticated() {
+        return Err(GITrelloError::NotAuthenticated)
+    }
+
+    let board_repository_service = BoardRepositoryService::new(&state, &user)?;
+    let board_repository = board_repository_service.get_by_id_for_mutation(id).await?;
+
+    let column_states = json
+        .into_inner()
+        .into_iter()
+        .map(|column_state| (column_state.column_id, column_state.state))
+        .collect();
+
+    let column_state_service = ColumnStateService::new(&state)?;
+    let column_states = column_state_service.replace(&board_repository, column_states).await?;
+
+    Ok(HttpResponse::Ok().json(to_response(column_states)))
+}
@@ -0,0 +1,49 @@
+use actix_web::{web, HttpResponse, HttpRequest};
+
+use crate::entities::gitrello_service::GITrelloService;
+use crate::entities::user::User;
+use crate::errors::GITrelloError;
+use crate::services::board_repository_service::BoardRepositoryService;
+use crate::services::github_issue_service::GithubIssueService;
+use crate::state::State;
+use crate::value_objects::request_data::github_issue::NewGithubIssueRequest;
+use crate::value_objects::response_data::github_issue::GithubIssueResponse;
+
+#[post("/api/v1/board-repositories/{id}/issues")]
+pub async fn create_github_issue(
+    req: HttpRequest,
+    web::Path((id, )): web::Path<(i32, )>,
+    json: web::Json<NewGithubIssueRequest>,
+    state: web::Data<State>,
+) -> Result<HttpResponse, GITrelloError>
+{
+    let github_issue_service = GithubIssueService::new(&state)?;
+
+    let gitrello_service = GITrelloService::from_request_headers(req.headers());
+    let board_repository = match gitrello_service.is_authenticated(state.gitrello_access_token.as_str()) {
+        true => github_issue_service.get_board_repository(id).await?,
+        false => {
+            let user = User::from_request_extensions(req.extensions());
+            if !user.is_authenticated() {
+                return Err(GITrelloError::NotAuthenticated)
+            }
+
+            let board_repository_service = BoardRepositoryService::new(&state, &user)?;
+            board_repository_service.get_by_id_for_mutation(id).await?
+        },
+    };
+
+    let (issue, issue_ticket) = github_issue_service
+        .create_from_ticket(&board_repository, json.ticket_id, json.title.as_str(), json.body.as_str())
+        .await?;
+
+    let response_data = GithubIssueResponse {
+        board_repository_id: issue_ticket.board_repository_id,
+        ticket_id: issue_ticket.ticket_id.to_string(),
+        issue_id: issue.id.to_string(),
+        issue_number: issue.number,
+        html_url: issue.html_url,
+    };
+
+    Ok(HttpResponse::Created().json(response_data))
+}
@@ -21,14 +21,15 @@ pub async fn create_github_profile(
 
     let github_profile_service = GithubProfileService::new(&state)?;
     let github_profile = github_profile_service
-        .create(user.id.expect("already checked"), json.access_token.as_str())
+        .create(user.id.expect("already checked"), json.access_token.as_str(), json.github_api_url.clone())
         .await?;
 
     let response_data = GithubProfileResponse {
         id: github_profile.id,
         user_id: github_profile.user_id.to_string(),
         github_user_id: github_profile.github_user_id.to_string(),
-        github_login: github_profile.github_login
+        github_login: github_profile.github_login,
+        github_api_url: github_profile.github_api_url,
     };
 
     Ok(HttpResponse::Created().json(response_data))
@@ -54,7 +55,8 @@ pub async fn get_github_profile(
         id: github_profile.id,
         user_id: github_profile.user_id.to_string(),
         github_user_id: github_profile.github_user_id.to_string(),
-        github_login: github_profile.github_login
+        github_login: github_profile.github_login,
+        github_api_url: github_profile.github_api_url,
     };
 
     Ok(HttpResponse::Ok().json(response_data))
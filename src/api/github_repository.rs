@@ -1,19 +1,22 @@
-use actix::Actor;
 use actix_web::{web, HttpResponse, HttpRequest};
 
 use crate::entities::user::User;
 use crate::errors::GITrelloError;
-use crate::models::github_profile::GithubProfile;
-use crate::services::github_api_client::GitHubAPIClient;
-use crate::services::repositories::github_profile::{
-    GithubProfileRepository, GetGithubProfileByUserIdMessage,
+use crate::services::github_repository_service::{
+    GithubRepositoryService, AFFILIATIONS, DEFAULT_PER_PAGE,
 };
 use crate::state::State;
+use crate::value_objects::request_data::github_repository::GetGithubRepositoriesQueryParams;
 use crate::value_objects::response_data::github_repository::GetGithubRepositoryResponse;
 
+const TOTAL_COUNT_HEADER: &str = "X-Total-Count";
+// Set when GitHub returned more repositories than are fetched, so the total and the pages are incomplete
+const TRUNCATED_HEADER: &str = "X-Total-Count-Truncated";
+
 #[get("/api/v1/github-repositories")]
 pub async fn get_github_repositories(
     req: HttpRequest,
+    web::Query(query_params): web::Query<GetGithubRepositoriesQueryParams>,
     state: web::Data<State>,
 ) -> Result<HttpResponse, GITrelloError>
 {
@@ -22,15 +25,25 @@ pub async fn get_github_repositories(
         return Err(GITrelloError::NotAuthenticated)
     }
 
-    let connection = state.get_db_connection()?;
-    let repository_actor = GithubProfileRepository::new(connection).start();
-    let github_profile: GithubProfile = repository_actor
-        .send(GetGithubProfileByUserIdMessage { user_id: user.id.expect("already checked") })
-        .await
-        .map_err(|source| GITrelloError::ActorError { source })??;
-    
-    let github_service = GitHubAPIClient::new(github_profile.access_token.as_str());
-    let repositories = github_service.get_repositories().await?;
+    let affiliations: Vec<String> = match &query_params.affiliation {
+        Some(affiliation) => affiliation
+            .split(',')
+            .map(|affiliation| affiliation.trim().to_string())
+            .filter(|affiliation| !affiliation.is_empty())
+            .collect(),
+        None => AFFILIATIONS.iter().map(|affiliation| affiliation.to_string()).collect(),
+    };
+
+    let github_repository_service = GithubRepositoryService::new(&state, &user);
+    let (repositories, total, is_truncated) = github_repository_service
+        .get_page(
+            &affiliations,
+            query_params.visibility.as_deref().unwrap_or("all"),
+            query_params.search.as_deref(),
+            query_params.page.unwrap_or(1),
+            query_params.per_page.unwrap_or(DEFAULT_PER_PAGE),
+        )
+        .await?;
 
     let mut response_data: Vec<GetGithubRepositoryResponse> = Vec::new();
     for repo in repositories {
@@ -41,5 +54,10 @@ pub async fn get_github_repositories(
         });
     }
 
-    Ok(HttpResponse::Ok().json(response_data))
+    Ok(
+        HttpResponse::Ok()
+            .header(TOTAL_COUNT_HEADER, total.to_string())
+            .header(TRUNCATED_HEADER, is_truncated.to_string())
+            .json(response_data)
+    )
 }
@@ -2,6 +2,8 @@ use actix_web::{web, HttpResponse, HttpRequest};
 use futures::StreamExt;
 
 use crate::services::github_webhook_service::GithubWebhookProcessingService;
+use crate::services::webhook_delivery_service::WebhookDeliveryService;
+use crate::services::webhook_health_service::WebhookHealthService;
 use crate::state::State;
 use crate::errors::GITrelloError;
 
@@ -22,15 +24,71 @@ pub async fn github_webhook(
                 .to_str()
                 .map_err(|_| GITrelloError::InternalError)?;
 
+            let signature = headers
+                .get("X-Hub-Signature-256")
+                .and_then(|value| value.to_str().ok());
+
+            let webhook_id: i64 = headers
+                .get("X-GitHub-Hook-ID")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse().ok())
+                .ok_or(GITrelloError::InvalidSignature)?;
+
             let mut bytes = web::BytesMut::new();
             while let Some(item) = body.next().await {
                 bytes.extend_from_slice(&item.map_err(|_| GITrelloError::InternalError)?);
             }
 
             let service = GithubWebhookProcessingService::new(&state);
-            service.process(event_name, bytes).await?;
+            service.verify_signature(webhook_id, signature, &bytes).await?;
+
+            let result = process_delivery(&req, &service, &state, event_name, bytes).await;
+
+            // Only verified deliveries change health, skipped redeliveries do not change it either
+            if !matches!(result, Ok(false)) {
+                let webhook_health_service = WebhookHealthService::new(&state)?;
+                if let Err(e) = webhook_health_service.record(webhook_id, event_name, result.as_ref().err()).await {
+                    error!("Failed to record health of webhook {}: {}", webhook_id, e);
+                }
+            }
+            result?;
 
             Ok(HttpResponse::Ok().finish())
         }
     }
 }
+
+// Returns false when the delivery has already been processed before
+async fn process_delivery(
+    req: &HttpRequest,
+    service: &GithubWebhookProcessingService<'_>,
+    state: &web::Data<State>,
+    event_name: &str,
+    bytes: web::BytesMut,
+) -> Result<bool, GITrelloError>
+{
+    let headers = req.headers();
+
+    let delivery_id = headers
+        .get("X-GitHub-Delivery")
+        .and_then(|value| value.to_str().ok())
+        .ok_or(GITrelloError::BadRequest {
+            message: String::from("X-GitHub-Delivery header is missing"),
+        })?;
+
+    let webhook_delivery_service = WebhookDeliveryService::new(state)?;
+    let webhook_delivery = webhook_delivery_service
+        .receive(delivery_id, event_name, &bytes, headers)
+        .await?;
+
+    if webhook_delivery.is_processed() {
+        info!("Delivery {} has already been processed, skipping", delivery_id);
+        return Ok(false);
+    }
+
+    let result = service.process(event_name, bytes).await;
+    webhook_delivery_service.finish(webhook_delivery, &result).await?;
+    result?;
+
+    Ok(true)
+}
@@ -0,0 +1,68 @@
+use actix_web::{web, HttpResponse, HttpRequest};
+
+use crate::entities::user::User;
+use crate::errors::GITrelloError;
+use crate::models::import_job::ImportJob;
+use crate::services::board_repository_service::BoardRepositoryService;
+use crate::services::import_job_service::ImportJobService;
+use crate::state::State;
+use crate::value_objects::response_data::import_job::ImportJobResponse;
+
+fn to_response(import_job: ImportJob) -> ImportJobResponse {
+    ImportJobResponse {
+        id: import_job.id,
+        board_repository_id: import_job.board_repository_id,
+        status: import_job.status,
+        total: import_job.total,
+        imported: import_job.imported,
+        skipped: import_job.skipped,
+        error: import_job.error,
+        created_at: import_job.created_at,
+        finished_at: import_job.finished_at,
+    }
+}
+
+#[post("/api/v1/board-repositories/{id}/import-jobs")]
+pub async fn create_import_job(
+    req: HttpRequest,
+    web::Path((id, )): web::Path<(i32, )>,
+    state: web::Data<State>,
+) -> Result<HttpResponse, GITrelloError>
+{
+    let user = User::from_request_extensions(req.extensions());
+    if !user.is_authenticated() {
+        return Err(GITrelloError::NotAuthenticated)
+    }
+
+    let board_repository_service = BoardRepositoryService::new(&state, &user)?;
+    let board_repository = board_repository_service.get_by_id_for_mutation(id).await?;
+
+    let import_job_service = ImportJobService::new(&state)?;
+    let (import_job, is_created) = import_job_service.enqueue(&board_repository).await?;
+
+    match is_created {
+        true => Ok(HttpResponse::Created().json(to_response(import_job))),
+        _ => Ok(HttpResponse::Ok().json(to_response(import_job))),
+    }
+}
+
+#[get("/api/v1/board-repositories/{id}/import-jobs/{import_job_id}")]
+pub async fn get_import_job(
+    req: HttpRequest,
+    web::Path((id, import_job_id)): web::Path<(i32, i32)>,
+    state: web::Data<State>,
+) -> Result<HttpResponse, GITrelloError>
+{
+    let user = User::from_request_extensions(req.extensions());
+    if !user.is_authenticated() {
+        return Err(GITrelloError::NotAuthenticated)
+    }
+
+    let board_repository_service = BoardRepositoryService::new(&state, &user)?;
+    let board_repository = board_repository_service.get_by_id(id).await?;
+
+    let import_job_service = ImportJobService::new(&state)?;
+    let import_job = import_job_service.get_by_id(&board_repository, import_job_id).await?;
+
+    Ok(HttpResponse::Ok().json(to_response(import_job)))
+}
@@ -0,0 +1,113 @@
+use actix_web::{web, HttpResponse, HttpRequest};
+
+use crate::entities::user::User;
+use crate::errors::GITrelloError;
+use crate::models::issue_filter::IssueFilter;
+use crate::services::board_repository_service::BoardRepositoryService;
+use crate::services::issue_filter_service::IssueFilterService;
+use crate::state::State;
+use crate::value_objects::request_data::issue_filter::IssueFilterRequest;
+use crate::value_objects::response_data::issue_filter::IssueFilterResponse;
+
+fn to_response(issue_filter: IssueFilter) -> IssueFilterResponse {
+    IssueFilterResponse {
+        id: issue_filter.id,
+        board_repository_id: issue_filter.board_repository_id,
+        kind: issue_filter.kind,
+        value: issue_filter.value,
+    }
+}
+
+#[get("/api/v1/board-repositories/{id}/issue-filters")]
+pub async fn get_issue_filters(
+    req: HttpRequest,
+    web::Path((id, )): web::Path<(i32, )>,
+    state: web::Data<State>,
+) -> Result<HttpResponse, GITrelloError>
+{
+    let user = User::from_request_extensions(req.extensions());
+    if !user.is_authenticated() {
+        return Err(GITrelloError::NotAuthenticated)
+    }
+
+    let board_repository_service = BoardRepositoryService::new(&state, &user)?;
+    let board_repository = board_repository_service.get_by_id(id).await?;
+
+    let issue_filter_service = IssueFilterService::new(&state)?;
+    let issue_filters = issue_filter_service
+        .get_by_board_repositories(&[board_repository])
+        .await?;
+
+    let response_data: Vec<IssueFilterResponse> = issue_filters.into_iter().map(to_response).collect();
+
+    Ok(HttpResponse::Ok().json(response_data))
+}
+
+#[post("/api/v1/board-repositories/{id}/issue-filters")]
+pub async fn create_issue_filter(
+    req: HttpRequest,
+    web::Path((id, )): web::Path<(i32, )>,
+    json: web::Json<IssueFilterRequest>,
+    state: web::Data<State>,
+) -> Result<HttpResponse, GITrelloError>
+{
+    let user = User::from_request_extensions(req.extensions());
+    if !user.is_authenticated() {
+        return Err(GITrelloError::NotAuthenticated)
+    }
+
+    let board_repository_service = BoardRepositoryService::new(&state, &user)?;
+    let board_repository = board_repository_service.get_by_id_for_mutation(id).await?;
+
+    let issue_filter_service = IssueFilterService::new(&state)?;
+    let issue_filter = issue_filter_service
+        .create(&board_repository, json.kind.as_str(), json.value.as_str())
+        .await?;
+
+    Ok(HttpResponse::Created().json(to_response(issue_filter)))
+}
+
+#[put("/api/v1/board-repositories/{id}/issue-filters/{filter_id}")]
+pub async fn update_issue_filter(
+    req: HttpRequest,
+    web::Path((id, filter_id)): web::Path<(i32, i32)>,
+    json: web::Json<IssueFilterRequest>,
+    state: web::Data<State>,
+) -> Result<HttpResponse, GITrelloError>
+{
+    let user = User::from_request_extensions(req.extensions());
+    if !user.is_authenticated() {
+        return Err(GITrelloError::NotAuthenticated)
+    }
+
+    let board_repository_service = BoardRepositoryService::new(&state, &user)?;
+    let board_repository = board_repository_service.get_by_id_for_mutation(id).await?;
+
+    let issue_filter_service = IssueFilterService::new(&state)?;
+    let issue_filter = issue_filter_service
+        .update(&board_repository, filter_id, json.kind.as_str(), json.value.as_str())
+        .await?;
+
+    Ok(HttpResponse::Ok().json(to_response(issue_filter)))
+}
+
+#[delete("/api/v1/board-repositories/{id}/issue-filters/{filter_id}")]
+pub async fn delete_issue_filter(
+    req: HttpRequest,
+    web::Path((id, filter_id)): web::Path<(i32, i32)>,
+    state: web::Data<State>,
+) -> Result<HttpResponse, GITrelloError>
+{
+    let user = User::from_request_extensions(req.extensions());
+    if !user.is_authenticated() {
+        return Err(GITrelloError::NotAuthenticated)
+    }
+
+    let board_repository_service = BoardRepositoryService::new(&state, &user)?;
+    let board_repository = board_repository_service.get_by_id_for_mutation(id).await?;
+
+    let issue_filter_service = IssueFilterService::new(&state)?;
+    issue_filter_service.delete(&board_repository, filter_id).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -1,5 +1,13 @@
+pub mod admin;
 pub mod board_repository;
+pub mod column_state;
+pub mod github_issue;
 pub mod github_profile;
 pub mod github_repository;
+pub mod import_job;
 pub mod github_webhook;
+pub mod issue_filter;
 pub mod ping;
+pub mod ticket_update;
+pub mod webhook_delivery;
+pub mod webhook_health;
@@ -0,0 +1,27 @@
+use actix_web::{web, HttpResponse, HttpRequest};
+
+use crate::entities::gitrello_service::GITrelloService;
+use crate::errors::GITrelloError;
+use crate::services::ticket_update_service::TicketUpdateService;
+use crate::state::State;
+use crate::value_objects::request_data::ticket_update::TicketUpdateRequest;
+
+#[post("/api/v1/ticket-updates")]
+pub async fn create_ticket_update(
+    req: HttpRequest,
+    json: web::Json<TicketUpdateRequest>,
+    state: web::Data<State>,
+) -> Result<HttpResponse, GITrelloError>
+{
+    let gitrello_service = GITrelloService::from_request_headers(req.headers());
+    if !gitrello_service.is_authenticated(state.gitrello_access_token.as_str()) {
+        return Err(GITrelloError::NotAuthenticated)
+    }
+
+    let ticket_update_service = TicketUpdateService::new(&state)?;
+    ticket_update_service
+        .apply(json.ticket_id, json.title.as_deref(), json.column_id)
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
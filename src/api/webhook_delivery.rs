@@ -0,0 +1,46 @@
+use actix_web::{web, HttpResponse, HttpRequest};
+
+use crate::entities::user::User;
+use crate::errors::GITrelloError;
+use crate::services::board_repository_service::BoardRepositoryService;
+use crate::services::webhook_delivery_service::WebhookDeliveryService;
+use crate::state::State;
+use crate::value_objects::response_data::webhook_delivery::WebhookDeliveryResponse;
+
+#[get("/api/v1/board-repositories/{id}/webhook-deliveries")]
+pub async fn get_webhook_deliveries(
+    req: HttpRequest,
+    web::Path((id, )): web::Path<(i32, )>,
+    state: web::Data<State>,
+) -> Result<HttpResponse, GITrelloError>
+{
+    let user = User::from_request_extensions(req.extensions());
+    if !user.is_authenticated() {
+        return Err(GITrelloError::NotAuthenticated)
+    }
+
+    let board_repository_service = BoardRepositoryService::new(&state, &user)?;
+    let board_repository = board_repository_service.get_by_id(id).await?;
+
+    let webhook_delivery_service = WebhookDeliveryService::new(&state)?;
+    let webhook_deliveries = webhook_delivery_service
+        .get_by_board_repository(&board_repository)
+        .await?;
+
+    let response_data: Vec<WebhookDeliveryResponse> = webhook_deliveries
+        .into_iter()
+        .map(|webhook_delivery| WebhookDeliveryResponse {
+            id: webhook_delivery.id,
+            delivery_id: webhook_delivery.delivery_id,
+            event: webhook_delivery.event,
+            action: webhook_delivery.action,
+            repository_owner: webhook_delivery.repository_owner,
+            repository_name: webhook_delivery.repository_name,
+            received_at: webhook_delivery.received_at,
+            status: webhook_delivery.status,
+            error: webhook_delivery.error,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(response_data))
+}
@@ -0,0 +1,40 @@
+use actix_web::{web, HttpResponse, HttpRequest};
+use chrono::Utc;
+
+use crate::entities::user::User;
+use crate::errors::GITrelloError;
+use crate::services::board_repository_service::BoardRepositoryService;
+use crate::services::webhook_health_service::{get_health_status, WebhookHealthService};
+use crate::state::State;
+use crate::value_objects::response_data::webhook_health::WebhookHealthResponse;
+
+#[get("/api/v1/board-repositories/{id}/webhook-health")]
+pub async fn get_webhook_health(
+    req: HttpRequest,
+    web::Path((id, )): web::Path<(i32, )>,
+    state: web::Data<State>,
+) -> Result<HttpResponse, GITrelloError>
+{
+    let user = User::from_request_extensions(req.extensions());
+    if !user.is_authenticated() {
+        return Err(GITrelloError::NotAuthenticated)
+    }
+
+    let board_repository_service = BoardRepositoryService::new(&state, &user)?;
+    let board_repository = board_repository_service.get_by_id(id).await?;
+
+    let webhook_health_service = WebhookHealthService::new(&state)?;
+    let github_webhook = webhook_health_service
+        .get_by_board_repository(&board_repository)
+        .await?;
+
+    let response_data = WebhookHealthResponse {
+        webhook_id: github_webhook.webhook_id.to_string(),
+        status: String::from(get_health_status(&github_webhook, Utc::now())),
+        last_ping_at: github_webhook.last_ping_at,
+        last_delivery_at: github_webhook.last_delivery_at,
+        last_error: github_webhook.last_error,
+    };
+
+    Ok(HttpResponse::Ok().json(response_data))
+}
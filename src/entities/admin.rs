@@ -0,0 +1,46 @@
+use actix_web::http::HeaderMap;
+
+const ADMIN_TOKEN_HEADER: &str = "X-Admin-Token";
+
+pub struct Admin {
+    pub token: Option<String>,
+}
+
+impl Admin {
+    pub fn from_request_headers(headers: &HeaderMap) -> Self {
+        let token = headers
+            .get(ADMIN_TOKEN_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string());
+
+        Self { token }
+    }
+
+    pub fn is_authenticated(&self, admin_token: &str) -> bool {
+        match &self.token {
+            Some(token) => !admin_token.is_empty() && token == admin_token,
+            None => false,
+        }
+    }
+}
+
+#[test]
+fn test_is_authenticated() {
+    let admin = Admin { token: Some(String::from("admin-token")) };
+    assert!(admin.is_authenticated("admin-token"));
+}
+
+#[test]
+fn test_is_not_authenticated() {
+    let admin = Admin { token: Some(String::from("wrong-token")) };
+    assert!(!admin.is_authenticated("admin-token"));
+
+    let admin = Admin { token: None };
+    assert!(!admin.is_authenticated("admin-token"));
+}
+
+#[test]
+fn test_is_not_authenticated_when_admin_token_is_not_configured() {
+    let admin = Admin { token: Some(String::new()) };
+    assert!(!admin.is_authenticated(""));
+}
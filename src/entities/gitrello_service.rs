@@ -0,0 +1,41 @@
+use actix_web::http::HeaderMap;
+
+// GITrello authenticates with the same token this service uses to call GITrello
+const SERVICE_TOKEN_HEADER: &str = "GITHUB_INTEGRATION_SERVICE_TOKEN";
+
+pub struct GITrelloService {
+    pub token: Option<String>,
+}
+
+impl GITrelloService {
+    pub fn from_request_headers(headers: &HeaderMap) -> Self {
+        let token = headers
+            .get(SERVICE_TOKEN_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string());
+
+        Self { token }
+    }
+
+    pub fn is_authenticated(&self, access_token: &str) -> bool {
+        match &self.token {
+            Some(token) => !access_token.is_empty() && token == access_token,
+            None => false,
+        }
+    }
+}
+
+#[test]
+fn test_is_authenticated() {
+    let gitrello_service = GITrelloService { token: Some(String::from("access-token")) };
+    assert!(gitrello_service.is_authenticated("access-token"));
+}
+
+#[test]
+fn test_is_not_authenticated() {
+    let gitrello_service = GITrelloService { token: Some(String::from("wrong-token")) };
+    assert!(!gitrello_service.is_authenticated("access-token"));
+
+    let gitrello_service = GITrelloService { token: None };
+    assert!(!gitrello_service.is_authenticated("access-token"));
+}
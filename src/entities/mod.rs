@@ -1 +1,3 @@
+pub mod admin;
+pub mod gitrello_service;
 pub mod user;
@@ -29,7 +29,9 @@ pub enum GITrelloError {
         source: r2d2::Error,
     },
     HttpRequestError {
-        #[from]
+        source: reqwest::Error,
+    },
+    HttpTimeoutError {
         source: reqwest::Error,
     },
     GitHubAPIClientError {
@@ -37,6 +39,7 @@ pub enum GITrelloError {
     },
     GITrelloAPIClientError {
         message: String,
+        status_code: u16,
     },
     NotAuthenticated,
     AlreadyExists {
@@ -45,8 +48,37 @@ pub enum GITrelloError {
     NotFound {
         message: String,
     },
+    BadRequest {
+        message: String,
+    },
     PermissionDenied,
+    InvalidSignature,
     InternalError,
+    RateLimitExceeded {
+        retry_after: i64,
+    },
+    MultipleErrors {
+        errors: Vec<GITrelloError>,
+    },
+}
+
+impl GITrelloError {
+    // Timeouts are told apart from other failed requests, the remote side may still process them
+    pub fn from_http_error(source: reqwest::Error) -> Self {
+        match source.is_timeout() {
+            true => Self::HttpTimeoutError { source },
+            false => Self::HttpRequestError { source },
+        }
+    }
+
+    // Lets batch operations process every item and still report all failures to the caller
+    pub fn aggregate(mut errors: Vec<Self>) -> Result<(), Self> {
+        match errors.len() {
+            0 => Ok(()),
+            1 => Err(errors.remove(0)),
+            _ => Err(Self::MultipleErrors { errors }),
+        }
+    }
 }
 
 impl fmt::Display for GITrelloError {
@@ -56,13 +88,23 @@ impl fmt::Display for GITrelloError {
             Self::DieselError { source} => write!(f, "{}", source.to_string()),
             Self::R2D2Error { source} => write!(f, "{}", source.to_string()),
             Self::HttpRequestError { source } => write!(f, "{}", source.to_string()),
+            Self::HttpTimeoutError { source } => write!(f, "Request timed out: {}", source.to_string()),
             Self::GitHubAPIClientError { message } => write!(f, "{}", message),
-            Self::GITrelloAPIClientError { message } => write!(f, "{}", message),
+            Self::GITrelloAPIClientError { message, status_code: _ } => write!(f, "{}", message),
             Self::NotAuthenticated => write!(f, "Authentication required"),
             Self::InternalError => write!(f, "Internal Server Error"),
             Self::AlreadyExists { message } => write!(f, "{}", message),
             Self::NotFound { message } => write!(f, "{}", message),
-            Self::PermissionDenied  => write!(f, "Permission denied")
+            Self::BadRequest { message } => write!(f, "{}", message),
+            Self::PermissionDenied  => write!(f, "Permission denied"),
+            Self::InvalidSignature => write!(f, "Webhook signature is missing or invalid"),
+            Self::RateLimitExceeded { retry_after } => {
+                write!(f, "GitHub API rate limit exceeded, retry in {} seconds", retry_after)
+            },
+            Self::MultipleErrors { errors } => {
+                let messages: Vec<String> = errors.iter().map(|error| error.to_string()).collect();
+                write!(f, "{}", messages.join("; "))
+            },
         }
     }
 }
@@ -71,9 +113,13 @@ impl error::ResponseError for GITrelloError {
     fn status_code(&self) -> http::StatusCode {
         match self {
             Self::NotAuthenticated => http::StatusCode::UNAUTHORIZED,
+            Self::InvalidSignature => http::StatusCode::UNAUTHORIZED,
             Self::AlreadyExists { message: _ } => http::StatusCode::BAD_REQUEST,
             Self::NotFound { message: _ } => http::StatusCode::NOT_FOUND,
+            Self::BadRequest { message: _ } => http::StatusCode::BAD_REQUEST,
             Self::PermissionDenied => http::StatusCode::FORBIDDEN,
+            Self::RateLimitExceeded { retry_after: _ } => http::StatusCode::TOO_MANY_REQUESTS,
+            Self::HttpTimeoutError { source: _ } => http::StatusCode::GATEWAY_TIMEOUT,
             _ => http::StatusCode::INTERNAL_SERVER_ERROR
         }
     }
@@ -90,12 +136,22 @@ impl error::ResponseError for GITrelloError {
             Self::InternalError => 105,
             Self::AlreadyExists { message: _ } => 106,
             Self::NotFound { message: _ } => 107,
-            Self::GITrelloAPIClientError { message: _ } => 108,
+            Self::GITrelloAPIClientError { message: _, status_code: _ } => 108,
             Self::PermissionDenied => 109,
             Self::ActorError { source: _ } => 110,
+            Self::InvalidSignature => 111,
+            Self::BadRequest { message: _ } => 112,
+            Self::RateLimitExceeded { retry_after: _ } => 113,
+            Self::HttpTimeoutError { source: _ } => 114,
+            Self::MultipleErrors { errors: _ } => 115,
         };
 
-        ResponseBuilder::new(self.status_code())
+        let mut response = ResponseBuilder::new(self.status_code());
+        if let Self::RateLimitExceeded { retry_after } = self {
+            response.set_header(http::header::RETRY_AFTER, retry_after.to_string());
+        }
+
+        response
             .set_header(http::header::CONTENT_TYPE, "application/json")
             .json(ErrorResponse { error_message: self.to_string(), error_code })
     }
@@ -3,9 +3,13 @@
 #[macro_use] extern crate log;
 extern crate pretty_env_logger;
 
+use actix::Actor;
 use actix_cors::Cors;
-use actix_web::{middleware::Logger, App, HttpServer};
+use actix_web::{middleware::Logger, web::Data, App, HttpServer};
 
+use services::import_job_worker::ImportJobWorker;
+use services::ticket_outbox_worker::TicketOutboxWorker;
+use services::webhook_reconciliation_worker::WebhookReconciliationWorker;
 use state::get_state;
 
 mod api;
@@ -24,6 +28,10 @@ async fn main() -> std::io::Result<()> {
 
     pretty_env_logger::init();
 
+    TicketOutboxWorker::new(Data::new(state.clone())).start();
+    ImportJobWorker::new(Data::new(state.clone())).start();
+    WebhookReconciliationWorker::new(Data::new(state.clone())).start();
+
     HttpServer::new(move || {
         App::new()
             .data(state.clone())
@@ -33,15 +41,37 @@ async fn main() -> std::io::Result<()> {
                 .finish()
             )
             .wrap(Logger::default())
+            .service(api::admin::get_ticket_outbox)
+            .service(api::admin::retry_ticket_outbox_item)
+            .service(api::admin::replay_webhook_delivery)
+            .service(api::admin::replay_webhook_deliveries)
+            .service(api::admin::reconcile_webhooks)
             .service(api::board_repository::create_or_update_board_repository)
             .service(api::board_repository::get_board_repository)
+            .service(api::board_repository::update_board_repository)
             .service(api::board_repository::delete_board_repository)
+            .service(api::board_repository::get_board_repositories)
+            .service(api::board_repository::add_board_repository)
+            .service(api::board_repository::remove_board_repository)
+            .service(api::board_repository::preview_ticket_template)
+            .service(api::column_state::get_column_states)
+            .service(api::column_state::update_column_states)
+            .service(api::github_issue::create_github_issue)
             .service(api::github_profile::create_github_profile)
             .service(api::github_profile::get_github_profile)
             .service(api::github_profile::delete_github_profile)
             .service(api::github_repository::get_github_repositories)
             .service(api::github_webhook::github_webhook)
+            .service(api::import_job::create_import_job)
+            .service(api::import_job::get_import_job)
+            .service(api::issue_filter::get_issue_filters)
+            .service(api::issue_filter::create_issue_filter)
+            .service(api::issue_filter::update_issue_filter)
+            .service(api::issue_filter::delete_issue_filter)
             .service(api::ping::ping)
+            .service(api::ticket_update::create_ticket_update)
+            .service(api::webhook_delivery::get_webhook_deliveries)
+            .service(api::webhook_health::get_webhook_health)
     })
     .bind("0.0.0.0:8001")?
     .run()
@@ -2,6 +2,15 @@ use serde::{Deserialize, Serialize};
 
 use crate::schema::board_repository;
 
+pub const EVENT_ISSUES: &str = "issues";
+pub const EVENT_ISSUE_COMMENT: &str = "issue_comment";
+pub const EVENT_PULL_REQUEST: &str = "pull_request";
+pub const EVENT_PUSH: &str = "push";
+pub const EVENTS: [&str; 4] = [EVENT_ISSUES, EVENT_ISSUE_COMMENT, EVENT_PULL_REQUEST, EVENT_PUSH];
+
+// Not selectable, every link needs it to follow renames, transfers and archival of the repository
+pub const EVENT_REPOSITORY: &str = "repository";
+
 #[table_name = "board_repository"]
 #[derive(Debug, Clone, Identifiable, Serialize, Queryable)]
 pub struct BoardRepository {
@@ -10,6 +19,13 @@ pub struct BoardRepository {
     pub board_id: i64,
     pub repository_name: String,
     pub repository_owner: String,
+    pub sync_pull_requests: bool,
+    pub allowed_bot_logins: Vec<String>,
+    pub repository_id: Option<i64>,
+    pub is_read_only: bool,
+    pub events: Vec<String>,
+    pub ticket_title_template: Option<String>,
+    pub ticket_body_template: Option<String>,
 }
 
 #[table_name = "board_repository"]
@@ -19,4 +35,27 @@ pub struct NewBoardRepository {
     pub board_id: i64,
     pub repository_name: String,
     pub repository_owner: String,
+    pub repository_id: Option<i64>,
+}
+
+#[table_name = "board_repository"]
+#[derive(Debug, Clone, Default, Deserialize, AsChangeset)]
+pub struct UpdateBoardRepository {
+    pub sync_pull_requests: Option<bool>,
+    pub allowed_bot_logins: Option<Vec<String>>,
+    pub is_read_only: Option<bool>,
+    pub events: Option<Vec<String>>,
+    pub ticket_title_template: Option<Option<String>>,
+    pub ticket_body_template: Option<Option<String>>,
+}
+
+impl UpdateBoardRepository {
+    pub fn is_empty(&self) -> bool {
+        self.sync_pull_requests.is_none() &&
+            self.allowed_bot_logins.is_none() &&
+            self.is_read_only.is_none() &&
+            self.events.is_none() &&
+            self.ticket_title_template.is_none() &&
+            self.ticket_body_template.is_none()
+    }
 }
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+use crate::schema::column_state;
+
+pub const STATE_OPEN: &str = "open";
+pub const STATE_CLOSED: &str = "closed";
+
+pub const STATES: [&str; 2] = [STATE_OPEN, STATE_CLOSED];
+
+#[table_name = "column_state"]
+#[derive(Debug, Clone, Identifiable, Serialize, Queryable)]
+pub struct ColumnState {
+    pub id: i32,
+    pub board_repository_id: i32,
+    pub column_id: i64,
+    pub state: String,
+}
+
+#[table_name = "column_state"]
+#[derive(Debug, Clone, Serialize, Deserialize, Insertable)]
+pub struct NewColumnState {
+    pub board_repository_id: i32,
+    pub column_id: i64,
+    pub state: String,
+}
@@ -9,6 +9,7 @@ pub struct GithubProfile {
     pub github_user_id: i64,
     pub github_login: String,
     pub access_token: String,
+    pub github_api_url: Option<String>,
 }
 
 #[table_name = "github_profile"]
@@ -18,4 +19,5 @@ pub struct NewGithubProfile {
     pub github_user_id: i64,
     pub github_login: String,
     pub access_token: String,
+    pub github_api_url: Option<String>,
 }
@@ -1,7 +1,12 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::schema::github_webhook;
 
+pub const HEALTH_ACTIVE: &str = "active";
+pub const HEALTH_STALE: &str = "stale";
+pub const HEALTH_FAILING: &str = "failing";
+
 #[table_name = "github_webhook"]
 #[derive(Debug, Clone, Identifiable, Serialize, Queryable)]
 pub struct GithubWebhook {
@@ -9,6 +14,10 @@ pub struct GithubWebhook {
     pub webhook_id: i64,
     pub url: String,
     pub board_repository_id: i32,
+    pub secret: String,
+    pub last_ping_at: Option<DateTime<Utc>>,
+    pub last_delivery_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
 }
 
 #[table_name = "github_webhook"]
@@ -17,6 +26,15 @@ pub struct NewGithubWebhook {
     pub webhook_id: i64,
     pub url: String,
     pub board_repository_id: i32,
+    pub secret: String,
+}
+
+#[table_name = "github_webhook"]
+#[derive(Debug, Clone, Default, AsChangeset)]
+pub struct UpdateGithubWebhookHealth {
+    pub last_ping_at: Option<DateTime<Utc>>,
+    pub last_delivery_at: Option<DateTime<Utc>>,
+    pub last_error: Option<Option<String>>,
 }
 
 #[derive(Deserialize, Queryable)]
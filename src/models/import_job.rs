@@ -0,0 +1,87 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::schema::import_job;
+
+pub const STATUS_PENDING: &str = "pending";
+pub const STATUS_RUNNING: &str = "running";
+pub const STATUS_FINISHED: &str = "finished";
+pub const STATUS_FAILED: &str = "failed";
+
+// Progress is saved after every page, a running job silent for longer was interrupted by a crash or restart
+const STALE_AFTER_MINUTES: i64 = 60;
+
+pub fn get_stale_before(now: DateTime<Utc>) -> DateTime<Utc> {
+    now - Duration::minutes(STALE_AFTER_MINUTES)
+}
+
+#[table_name = "import_job"]
+#[derive(Debug, Clone, Identifiable, Serialize, Queryable)]
+pub struct ImportJob {
+    pub id: i32,
+    pub board_repository_id: i32,
+    pub status: String,
+    pub total: i32,
+    pub imported: i32,
+    pub skipped: i32,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ImportJob {
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        match self.status.as_str() {
+            STATUS_PENDING => true,
+            STATUS_RUNNING => self.updated_at > get_stale_before(now),
+            _ => false,
+        }
+    }
+}
+
+#[table_name = "import_job"]
+#[derive(Debug, Clone, Serialize, Deserialize, Insertable)]
+pub struct NewImportJob {
+    pub board_repository_id: i32,
+    pub status: String,
+}
+
+#[table_name = "import_job"]
+#[derive(Debug, Clone, Default, AsChangeset)]
+pub struct UpdateImportJob {
+    pub status: Option<String>,
+    pub total: Option<i32>,
+    pub imported: Option<i32>,
+    pub skipped: Option<i32>,
+    pub error: Option<Option<String>>,
+    pub finished_at: Option<Option<DateTime<Utc>>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[test]
+fn test_import_job_is_active() {
+    let now = Utc::now();
+    let mut import_job = ImportJob {
+        id: 1,
+        board_repository_id: 1,
+        status: String::from(STATUS_PENDING),
+        total: 0,
+        imported: 0,
+        skipped: 0,
+        error: None,
+        created_at: now - Duration::hours(2),
+        finished_at: None,
+        updated_at: now - Duration::hours(2),
+    };
+    assert!(import_job.is_active(now));
+
+    import_job.status = String::from(STATUS_RUNNING);
+    assert!(!import_job.is_active(now));
+
+    import_job.updated_at = now - Duration::minutes(1);
+    assert!(import_job.is_active(now));
+
+    import_job.status = String::from(STATUS_FINISHED);
+    assert!(!import_job.is_active(now));
+}
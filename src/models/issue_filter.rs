@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+use crate::schema::issue_filter;
+
+pub const KIND_INCLUDE_LABEL: &str = "include_label";
+pub const KIND_EXCLUDE_LABEL: &str = "exclude_label";
+pub const KIND_INCLUDE_AUTHOR: &str = "include_author";
+pub const KIND_EXCLUDE_AUTHOR: &str = "exclude_author";
+pub const KIND_TITLE_PATTERN: &str = "title_pattern";
+
+pub const KINDS: [&str; 5] = [
+    KIND_INCLUDE_LABEL,
+    KIND_EXCLUDE_LABEL,
+    KIND_INCLUDE_AUTHOR,
+    KIND_EXCLUDE_AUTHOR,
+    KIND_TITLE_PATTERN,
+];
+
+#[table_name = "issue_filter"]
+#[derive(Debug, Clone, Identifiable, Serialize, Queryable)]
+pub struct IssueFilter {
+    pub id: i32,
+    pub board_repository_id: i32,
+    pub kind: String,
+    pub value: String,
+}
+
+#[table_name = "issue_filter"]
+#[derive(Debug, Clone, Serialize, Deserialize, Insertable)]
+pub struct NewIssueFilter {
+    pub board_repository_id: i32,
+    pub kind: String,
+    pub value: String,
+}
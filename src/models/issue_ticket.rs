@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+use crate::schema::issue_ticket;
+
+#[table_name = "issue_ticket"]
+#[derive(Debug, Clone, Identifiable, Serialize, Queryable)]
+pub struct IssueTicket {
+    pub id: i32,
+    pub board_repository_id: i32,
+    pub issue_id: i64,
+    pub issue_number: i32,
+    pub ticket_id: i64,
+}
+
+#[table_name = "issue_ticket"]
+#[derive(Debug, Clone, Serialize, Deserialize, Insertable)]
+pub struct NewIssueTicket {
+    pub board_repository_id: i32,
+    pub issue_id: i64,
+    pub issue_number: i32,
+    pub ticket_id: i64,
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+use crate::schema::ticket_comment;
+
+#[table_name = "ticket_comment"]
+#[derive(Debug, Clone, Identifiable, Serialize, Queryable)]
+pub struct TicketComment {
+    pub id: i32,
+    pub issue_ticket_id: i32,
+    pub comment_id: i64,
+    pub ticket_comment_id: i64,
+}
+
+#[table_name = "ticket_comment"]
+#[derive(Debug, Clone, Serialize, Deserialize, Insertable)]
+pub struct NewTicketComment {
+    pub issue_ticket_id: i32,
+    pub comment_id: i64,
+    pub ticket_comment_id: i64,
+}
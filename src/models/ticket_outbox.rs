@@ -0,0 +1,39 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::schema::ticket_outbox;
+
+pub const STATUS_PENDING: &str = "pending";
+pub const STATUS_DEAD: &str = "dead";
+
+#[table_name = "ticket_outbox"]
+#[derive(Debug, Clone, Identifiable, Serialize, Queryable)]
+pub struct TicketOutboxItem {
+    pub id: i32,
+    pub board_repository_id: i32,
+    pub board_id: i64,
+    pub title: String,
+    pub body: String,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub issue_id: Option<i64>,
+    pub issue_number: Option<i32>,
+}
+
+#[table_name = "ticket_outbox"]
+#[derive(Debug, Clone, Serialize, Deserialize, Insertable)]
+pub struct NewTicketOutboxItem {
+    pub board_repository_id: i32,
+    pub board_id: i64,
+    pub title: String,
+    pub body: String,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub issue_id: Option<i64>,
+    pub issue_number: Option<i32>,
+}
@@ -0,0 +1,43 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::schema::webhook_delivery;
+
+pub const STATUS_RECEIVED: &str = "received";
+pub const STATUS_PROCESSED: &str = "processed";
+pub const STATUS_FAILED: &str = "failed";
+
+#[table_name = "webhook_delivery"]
+#[derive(Debug, Clone, Identifiable, Serialize, Queryable)]
+pub struct WebhookDelivery {
+    pub id: i32,
+    pub delivery_id: String,
+    pub event: String,
+    pub action: Option<String>,
+    pub repository_owner: Option<String>,
+    pub repository_name: Option<String>,
+    pub received_at: DateTime<Utc>,
+    pub status: String,
+    pub error: Option<String>,
+    pub payload: Option<String>,
+    pub headers: Option<String>,
+}
+
+impl WebhookDelivery {
+    pub fn is_processed(&self) -> bool {
+        self.status == STATUS_PROCESSED
+    }
+}
+
+#[table_name = "webhook_delivery"]
+#[derive(Debug, Clone, Serialize, Deserialize, Insertable)]
+pub struct NewWebhookDelivery {
+    pub delivery_id: String,
+    pub event: String,
+    pub action: Option<String>,
+    pub repository_owner: Option<String>,
+    pub repository_name: Option<String>,
+    pub status: String,
+    pub payload: Option<String>,
+    pub headers: Option<String>,
+}
@@ -5,6 +5,22 @@ table! {
         board_id -> Int8,
         repository_name -> Varchar,
         repository_owner -> Varchar,
+        sync_pull_requests -> Bool,
+        allowed_bot_logins -> Array<Text>,
+        repository_id -> Nullable<Int8>,
+        is_read_only -> Bool,
+        events -> Array<Text>,
+        ticket_title_template -> Nullable<Text>,
+        ticket_body_template -> Nullable<Text>,
+    }
+}
+
+table! {
+    column_state (id) {
+        id -> Int4,
+        board_repository_id -> Int4,
+        column_id -> Int8,
+        state -> Varchar,
     }
 }
 
@@ -15,6 +31,7 @@ table! {
         github_user_id -> Int8,
         github_login -> Varchar,
         access_token -> Varchar,
+        github_api_url -> Nullable<Text>,
     }
 }
 
@@ -24,14 +41,107 @@ table! {
         webhook_id -> Int8,
         url -> Varchar,
         board_repository_id -> Int4,
+        secret -> Varchar,
+        last_ping_at -> Nullable<Timestamptz>,
+        last_delivery_at -> Nullable<Timestamptz>,
+        last_error -> Nullable<Text>,
+    }
+}
+
+table! {
+    import_job (id) {
+        id -> Int4,
+        board_repository_id -> Int4,
+        status -> Varchar,
+        total -> Int4,
+        imported -> Int4,
+        skipped -> Int4,
+        error -> Nullable<Text>,
+        created_at -> Timestamptz,
+        finished_at -> Nullable<Timestamptz>,
+        updated_at -> Timestamptz,
+    }
+}
+
+table! {
+    issue_filter (id) {
+        id -> Int4,
+        board_repository_id -> Int4,
+        kind -> Varchar,
+        value -> Varchar,
+    }
+}
+
+table! {
+    issue_ticket (id) {
+        id -> Int4,
+        board_repository_id -> Int4,
+        issue_id -> Int8,
+        issue_number -> Int4,
+        ticket_id -> Int8,
+    }
+}
+
+table! {
+    ticket_comment (id) {
+        id -> Int4,
+        issue_ticket_id -> Int4,
+        comment_id -> Int8,
+        ticket_comment_id -> Int8,
+    }
+}
+
+table! {
+    ticket_outbox (id) {
+        id -> Int4,
+        board_repository_id -> Int4,
+        board_id -> Int8,
+        title -> Text,
+        body -> Text,
+        status -> Varchar,
+        attempts -> Int4,
+        next_attempt_at -> Timestamptz,
+        last_error -> Nullable<Text>,
+        created_at -> Timestamptz,
+        issue_id -> Nullable<Int8>,
+        issue_number -> Nullable<Int4>,
+    }
+}
+
+table! {
+    webhook_delivery (id) {
+        id -> Int4,
+        delivery_id -> Varchar,
+        event -> Varchar,
+        action -> Nullable<Varchar>,
+        repository_owner -> Nullable<Varchar>,
+        repository_name -> Nullable<Varchar>,
+        received_at -> Timestamptz,
+        status -> Varchar,
+        error -> Nullable<Text>,
+        payload -> Nullable<Text>,
+        headers -> Nullable<Text>,
     }
 }
 
 joinable!(board_repository -> github_profile (github_profile_id));
+joinable!(column_state -> board_repository (board_repository_id));
 joinable!(github_webhook -> board_repository (board_repository_id));
+joinable!(import_job -> board_repository (board_repository_id));
+joinable!(issue_filter -> board_repository (board_repository_id));
+joinable!(issue_ticket -> board_repository (board_repository_id));
+joinable!(ticket_comment -> issue_ticket (issue_ticket_id));
+joinable!(ticket_outbox -> board_repository (board_repository_id));
 
 allow_tables_to_appear_in_same_query!(
     board_repository,
+    column_state,
     github_profile,
     github_webhook,
+    import_job,
+    issue_filter,
+    issue_ticket,
+    ticket_comment,
+    ticket_outbox,
+    webhook_delivery,
 );
@@ -3,18 +3,21 @@ use actix_web::{web::Data, };
 
 use crate::entities::user::User;
 use crate::errors::GITrelloError;
-use crate::models::board_repository::{BoardRepository, NewBoardRepository};
+use crate::models::board_repository::{BoardRepository, NewBoardRepository, UpdateBoardRepository, EVENTS};
 use crate::models::github_webhook::GithubWebhook;
-use crate::services::github_api_client::GitHubAPIClient;
+#[cfg(test)]
+use crate::services::fakes::get_fake_board_repository;
+use crate::services::github_api_client::get_github_api_client;
 use crate::services::github_profile_service::GithubProfileService;
 use crate::services::github_webhook_service::GithubWebhookService;
-use crate::services::gitrello_api_client::GITRelloAPIClient;
+use crate::services::ticket_template::validate_template;
 use crate::services::repositories::github_webhook::{
     GetByRepositoryNameAndOwnerMessage, GithubWebhookRepository,
 };
 use crate::services::repositories::board_repository::{
     BoardRepositoryRepository, CreateBoardRepositoryMessage, DeleteBoardRepositoryMessage,
-    GetBoardRepositoryByBoardIdMessage, GetBoardRepositoryByIdMessage, UpdateRepositoryDataMessage,
+    GetBoardRepositoriesByBoardIdMessage, GetBoardRepositoryByIdMessage, UpdateBoardRepositoryMessage,
+    UpdateRepositoryDataMessage,
 };
 use crate::state::State;
 use crate::value_objects::gitrello_api::Permissions;
@@ -32,7 +35,18 @@ impl<'a> BoardRepositoryService<'a> {
         Ok(Self { actor, state, user })
     }
 
+    // Boards linked before several repositories were supported have a single one, the oldest is returned
     pub async fn get_by_board_id(&self, board_id: i64) -> Result<BoardRepository, GITrelloError> {
+        self.get_all_by_board_id(board_id)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| GITrelloError::NotFound {
+                message: format!("board_repository for board {} does not exist", board_id),
+            })
+    }
+
+    pub async fn get_all_by_board_id(&self, board_id: i64) -> Result<Vec<BoardRepository>, GITrelloError> {
         let permissions = self.get_permissions(board_id).await?;
         if !permissions.can_read {
             return Err(GITrelloError::PermissionDenied);
@@ -41,6 +55,40 @@ impl<'a> BoardRepositoryService<'a> {
         self._get_by_board_id(board_id).await
     }
 
+    pub async fn get_by_id(&self, id: i32) -> Result<BoardRepository, GITrelloError> {
+        let board_repository = self._get_by_id(id).await?;
+
+        let permissions = self.get_permissions(board_repository.board_id).await?;
+        if !permissions.can_read {
+            return Err(GITrelloError::PermissionDenied);
+        }
+
+        Ok(board_repository)
+    }
+
+    pub async fn get_by_id_for_mutation(&self, id: i32) -> Result<BoardRepository, GITrelloError> {
+        let board_repository = self._get_by_id(id).await?;
+
+        let permissions = self.get_permissions(board_repository.board_id).await?;
+        if !permissions.can_mutate {
+            return Err(GITrelloError::PermissionDenied);
+        }
+
+        if board_repository.is_read_only {
+            return Err(GITrelloError::BadRequest {
+                message: format!(
+                    "Repository {}/{} is archived, board_repository {} is read-only",
+                    board_repository.repository_owner,
+                    board_repository.repository_name,
+                    board_repository.id,
+                ),
+            });
+        }
+
+        Ok(board_repository)
+    }
+
+    // Replaces the oldest link of the board unless the repository is already linked to it
     pub async fn create_or_update(
         &self,
         board_id: i64,
@@ -53,13 +101,14 @@ impl<'a> BoardRepositoryService<'a> {
             return Err(GITrelloError::PermissionDenied);
         }
 
-        let board_repository = self._get_by_board_id(board_id).await;
-        return match board_repository {
-            Ok(board_repository) => {
-                if board_repository.repository_name == repository_name &&
-                        board_repository.repository_owner == repository_owner {
-                    return Ok((board_repository, false));
-                }
+        let board_repositories = self._get_by_board_id(board_id).await?;
+        if let Some(board_repository) = find_board_repository(&board_repositories, repository_name, repository_owner) {
+            return Ok((board_repository.clone(), false));
+        }
+
+        match board_repositories.into_iter().next() {
+            Some(board_repository) => {
+                let repository_id = self.get_repository_id(repository_name, repository_owner).await?;
 
                 let github_webhook_service = GithubWebhookService::new(self.state, self.user).await?;
                 github_webhook_service
@@ -67,38 +116,102 @@ impl<'a> BoardRepositoryService<'a> {
                     .await?;
 
                 self
-                    .update_repository_data(board_repository, repository_name, repository_owner)
+                    .update_repository_data(
+                        board_repository,
+                        repository_name,
+                        repository_owner,
+                        Some(repository_id),
+                    )
                     .await
                     .map(|board_repository| (board_repository, false))
-            }
-            Err(e) => {
-                match e {
-                    GITrelloError::NotFound {message: _ } => {
-                        let github_profile_service = GithubProfileService::new(self.state)?;
-                        let github_profile = github_profile_service
-                            .get_by_user_id(self.user.id.expect("already checked"))
-                            .await?;
-
-                        let board_repository = self
-                            .create(github_profile.id, board_id, repository_name, repository_owner)
-                            .await?;
-
-                        let github_webhook_service = GithubWebhookService::new(self.state, self.user).await?;
-                        github_webhook_service
-                            .create_or_update(&board_repository, repository_name, repository_owner)
-                            .await?;
-
-                        Ok((board_repository, true))
-                    },
-                    _ => Err(e)
-                }
-            }
+            },
+            None => {
+                self.link(board_id, repository_name, repository_owner)
+                    .await
+                    .map(|board_repository| (board_repository, true))
+            },
+        }
+    }
+
+    // Links one more repository to the board, the existing link is returned if there is one
+    pub async fn add(
+        &self,
+        board_id: i64,
+        repository_name: &str,
+        repository_owner: &str,
+    ) -> Result<(BoardRepository, bool), GITrelloError>
+    {
+        let permissions = self.get_permissions(board_id).await?;
+        if !permissions.can_mutate {
+            return Err(GITrelloError::PermissionDenied);
+        }
+
+        let board_repositories = self._get_by_board_id(board_id).await?;
+        if let Some(board_repository) = find_board_repository(&board_repositories, repository_name, repository_owner) {
+            return Ok((board_repository.clone(), false));
+        }
+
+        self.link(board_id, repository_name, repository_owner)
+            .await
+            .map(|board_repository| (board_repository, true))
+    }
+
+    pub async fn update(
+        &self,
+        id: i32,
+        data: UpdateBoardRepository,
+    ) -> Result<BoardRepository, GITrelloError>
+    {
+        let board_repository = self.get_by_id_for_mutation(id).await?;
+
+        if data.is_empty() {
+            return Ok(board_repository);
+        }
+
+        let events = match data.events {
+            Some(events) => Some(validate_events(events)?),
+            None => None,
+        };
+        let is_events_changed = events.is_some() && events.as_ref() != Some(&board_repository.events);
+
+        let data = UpdateBoardRepository {
+            events,
+            ticket_title_template: validate_ticket_template(data.ticket_title_template)?,
+            ticket_body_template: validate_ticket_template(data.ticket_body_template)?,
+            ..data
         };
+
+        let board_repository = self.actor
+            .send(UpdateBoardRepositoryMessage { board_repository, data })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })??;
+
+        if is_events_changed {
+            let github_webhook_service = GithubWebhookService::new(self.state, self.user).await?;
+            github_webhook_service.update_events_by_board_repository(&board_repository).await?;
+        }
+
+        Ok(board_repository)
     }
 
     pub async fn delete(&self, id: i32) -> Result<(), GITrelloError> {
         let board_repository = self._get_by_id(id).await?;
+        self._delete(board_repository).await
+    }
+
+    pub async fn delete_from_board(&self, board_id: i64, id: i32) -> Result<(), GITrelloError> {
+        let board_repository = self._get_by_id(id).await?;
+        if board_repository.board_id != board_id {
+            return Err(GITrelloError::NotFound {
+                message: format!("board_repository {} does not exist on board {}", id, board_id),
+            });
+        }
 
+        self._delete(board_repository).await
+    }
+
+    // The GitHub hook is shared by all boards linked to the repository, it is deleted with the last link
+    async fn _delete(&self, board_repository: BoardRepository) -> Result<(), GITrelloError> {
         let permissions = self.get_permissions(board_repository.board_id).await?;
         if !permissions.can_mutate {
             return Err(GITrelloError::PermissionDenied);
@@ -123,7 +236,7 @@ impl<'a> BoardRepositoryService<'a> {
                 .get_by_user_id(self.user.id.expect("already checked"))
                 .await?;
 
-            let api_client = GitHubAPIClient::new(github_profile.access_token.as_str());
+            let api_client = get_github_api_client(self.state, &github_profile);
             api_client
                 .delete_webhook(
                     board_repository.repository_name.as_str(),
@@ -134,15 +247,31 @@ impl<'a> BoardRepositoryService<'a> {
         }
 
         self.actor
-            .send(DeleteBoardRepositoryMessage { id })
+            .send(DeleteBoardRepositoryMessage { id: board_repository.id })
             .await
-            .map_err(|source| GITrelloError::ActorError { source })?
+            .map_err(|source| GITrelloError::ActorError { source })??;
+
+        // Remaining boards may not need all events anymore, the link is gone already so failures are only logged
+        if github_webhooks.len() > 1 && !board_repository.is_read_only {
+            let webhook_id = github_webhooks.first().expect("already checked").webhook_id;
+            let github_webhook_service = GithubWebhookService::new(self.state, self.user).await?;
+            let result = github_webhook_service
+                .update_events(
+                    webhook_id,
+                    board_repository.repository_name.as_str(),
+                    board_repository.repository_owner.as_str(),
+                )
+                .await;
+            if let Err(e) = result {
+                error!("Failed to update events of webhook {}: {}", webhook_id, e);
+            }
+        }
+
+        Ok(())
     }
 
     async fn get_permissions(&self, board_id: i64) -> Result<Permissions, GITrelloError> {
-        let gitrello_api_client = GITRelloAPIClient::new(
-            self.state.gitrello_url.as_str(),
-        );
+        let gitrello_api_client = self.state.gitrello_api_client_factory.create(self.state, None);
 
         gitrello_api_client
             .get_board_permissions(
@@ -152,9 +281,26 @@ impl<'a> BoardRepositoryService<'a> {
             .await
     }
 
-    async fn _get_by_board_id(&self, board_id: i64) -> Result<BoardRepository, GITrelloError> {
+    async fn get_repository_id(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+    ) -> Result<i64, GITrelloError>
+    {
+        let github_profile_service = GithubProfileService::new(self.state)?;
+        let github_profile = github_profile_service
+            .get_by_user_id(self.user.id.expect("already checked"))
+            .await?;
+
+        let api_client = get_github_api_client(self.state, &github_profile);
+        let repository = api_client.get_repository(repository_name, repository_owner).await?;
+
+        Ok(repository.id)
+    }
+
+    async fn _get_by_board_id(&self, board_id: i64) -> Result<Vec<BoardRepository>, GITrelloError> {
         self.actor
-            .send(GetBoardRepositoryByBoardIdMessage { board_id })
+            .send(GetBoardRepositoriesByBoardIdMessage { board_id })
             .await
             .map_err(|source| GITrelloError::ActorError { source })?
     }
@@ -166,12 +312,46 @@ impl<'a> BoardRepositoryService<'a> {
             .map_err(|source| GITrelloError::ActorError { source })?
     }
 
+    async fn link(
+        &self,
+        board_id: i64,
+        repository_name: &str,
+        repository_owner: &str,
+    ) -> Result<BoardRepository, GITrelloError>
+    {
+        let github_profile_service = GithubProfileService::new(self.state)?;
+        let github_profile = github_profile_service
+            .get_by_user_id(self.user.id.expect("already checked"))
+            .await?;
+
+        let repository_id = self.get_repository_id(repository_name, repository_owner).await?;
+
+        let board_repository = self
+            .create(
+                github_profile.id,
+                board_id,
+                repository_name,
+                repository_owner,
+                Some(repository_id),
+            )
+            .await?;
+
+        // Reuses the hook if the repository is already linked to another board
+        let github_webhook_service = GithubWebhookService::new(self.state, self.user).await?;
+        github_webhook_service
+            .create_or_update(&board_repository, repository_name, repository_owner)
+            .await?;
+
+        Ok(board_repository)
+    }
+
     async fn create(
         &self,
         github_profile_id: i32,
         board_id: i64,
         repository_name: &str,
         repository_owner: &str,
+        repository_id: Option<i64>,
     ) -> Result<BoardRepository, GITrelloError>
     {
         self.actor
@@ -181,6 +361,7 @@ impl<'a> BoardRepositoryService<'a> {
                     board_id,
                     repository_name: repository_name.to_string(),
                     repository_owner: repository_owner.to_string(),
+                    repository_id,
                 },
             })
             .await
@@ -192,6 +373,7 @@ impl<'a> BoardRepositoryService<'a> {
         board_repository: BoardRepository,
         repository_name: &str,
         repository_owner: &str,
+        repository_id: Option<i64>,
     ) -> Result<BoardRepository, GITrelloError>
     {
         self.actor
@@ -199,8 +381,82 @@ impl<'a> BoardRepositoryService<'a> {
                 board_repository,
                 repository_name: repository_name.to_string(),
                 repository_owner: repository_owner.to_string(),
+                repository_id,
             })
             .await
             .map_err(|source| GITrelloError::ActorError { source })?
     }
 }
+
+// Unknown events are rejected rather than dropped, duplicates are removed
+pub fn validate_events(events: Vec<String>) -> Result<Vec<String>, GITrelloError> {
+    if let Some(event) = events.iter().find(|event| !EVENTS.contains(&event.as_str())) {
+        return Err(GITrelloError::BadRequest {
+            message: format!("{} is not a valid event, valid events are {}", event, EVENTS.join(", ")),
+        });
+    }
+
+    let mut events = events;
+    events.sort();
+    events.dedup();
+    Ok(events)
+}
+
+// An empty template resets it to the default one
+fn validate_ticket_template(template: Option<Option<String>>) -> Result<Option<Option<String>>, GITrelloError> {
+    match template {
+        Some(Some(template)) if template.trim().is_empty() => Ok(Some(None)),
+        Some(Some(template)) => {
+            validate_template(template.as_str())?;
+            Ok(Some(Some(template)))
+        },
+        template => Ok(template),
+    }
+}
+
+fn find_board_repository<'a>(
+    board_repositories: &'a [BoardRepository],
+    repository_name: &str,
+    repository_owner: &str,
+) -> Option<&'a BoardRepository>
+{
+    board_repositories
+        .iter()
+        .find(|board_repository| {
+            board_repository.repository_name == repository_name &&
+                board_repository.repository_owner == repository_owner
+        })
+}
+
+#[test]
+fn test_find_board_repository() {
+    let board_repositories = vec![
+        get_fake_board_repository(1, "frontend", &[]),
+        get_fake_board_repository(2, "backend", &[]),
+    ];
+
+    let board_repository = find_board_repository(&board_repositories, "backend", "gitrello");
+    assert_eq!(board_repository.map(|board_repository| board_repository.id), Some(2));
+    assert!(find_board_repository(&board_repositories, "backend", "someone-else").is_none());
+    assert!(find_board_repository(&board_repositories, "infra", "gitrello").is_none());
+}
+
+#[test]
+fn test_validate_events() {
+    let events = validate_events(vec![String::from("push"), String::from("issues"), String::from("push")]);
+    assert_eq!(events.ok(), Some(vec![String::from("issues"), String::from("push")]));
+
+    assert_eq!(validate_events(vec![]).ok(), Some(vec![]));
+    assert!(validate_events(vec![String::from("issues"), String::from("repository")]).is_err());
+}
+
+#[test]
+fn test_validate_ticket_template() {
+    assert_eq!(validate_ticket_template(None).ok(), Some(None));
+    assert_eq!(validate_ticket_template(Some(Some(String::from(" ")))).ok(), Some(Some(None)));
+    assert_eq!(
+        validate_ticket_template(Some(Some(String::from("[{repository}] {title}")))).ok(),
+        Some(Some(Some(String::from("[{repository}] {title}")))),
+    );
+    assert!(validate_ticket_template(Some(Some(String::from("{milestone}")))).is_err());
+}
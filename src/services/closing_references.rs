@@ -0,0 +1,34 @@
+use regex::Regex;
+
+// Same keywords GitHub uses to link pull requests and commits to the issues they close
+const CLOSING_REFERENCE_PATTERN: &str = r"(?i)\b(?:close[sd]?|fix(?:e[sd])?|resolve[sd]?):?\s+#(\d+)\b";
+
+pub fn parse_closing_references(text: &str) -> Vec<i32> {
+    let regex = Regex::new(CLOSING_REFERENCE_PATTERN).expect("pattern should be valid");
+
+    let mut issue_numbers: Vec<i32> = Vec::new();
+    for captures in regex.captures_iter(text) {
+        if let Ok(issue_number) = captures[1].parse::<i32>() {
+            if !issue_numbers.contains(&issue_number) {
+                issue_numbers.push(issue_number);
+            }
+        }
+    }
+
+    issue_numbers
+}
+
+#[test]
+fn test_parse_closing_references() {
+    assert_eq!(parse_closing_references("Fixes #42"), vec![42]);
+    assert_eq!(parse_closing_references("closes #1, resolved #2 and FIX: #3"), vec![1, 2, 3]);
+    assert_eq!(parse_closing_references("Fixed #7\n\nAlso fixes #7"), vec![7]);
+}
+
+#[test]
+fn test_parse_closing_references_ignores_other_references() {
+    assert!(parse_closing_references("See #42").is_empty());
+    assert!(parse_closing_references("Prefixes #42").is_empty());
+    assert!(parse_closing_references("Fixes octocat/hello-world#42").is_empty());
+    assert!(parse_closing_references("Fixes #").is_empty());
+}
@@ -0,0 +1,93 @@
+use std::collections::HashSet;
+
+use actix::{Actor, Addr};
+use actix_web::web::Data;
+
+use crate::errors::GITrelloError;
+use crate::models::board_repository::BoardRepository;
+use crate::models::column_state::{ColumnState, NewColumnState, STATES};
+use crate::services::repositories::column_state::{
+    ColumnStateRepository, GetColumnStatesByBoardRepositoryIdMessage, ReplaceColumnStatesMessage,
+};
+use crate::state::State;
+
+// Column the ticket has been moved to decides the issue state, unmapped columns leave the issue as is
+pub fn get_issue_state(column_states: &[ColumnState], column_id: i64) -> Option<&str> {
+    column_states
+        .iter()
+        .find(|column_state| column_state.column_id == column_id)
+        .map(|column_state| column_state.state.as_str())
+}
+
+pub struct ColumnStateService {
+    actor: Addr<ColumnStateRepository>,
+}
+
+impl ColumnStateService {
+    pub fn new(state: &Data<State>) -> Result<Self, GITrelloError> {
+        let connection = state.get_db_connection()?;
+        let actor = ColumnStateRepository::new(connection).start();
+        Ok(Self { actor })
+    }
+
+    pub async fn get_by_board_repository(
+        &self,
+        board_repository: &BoardRepository,
+    ) -> Result<Vec<ColumnState>, GITrelloError>
+    {
+        self.actor
+            .send(GetColumnStatesByBoardRepositoryIdMessage { board_repository_id: board_repository.id })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })?
+    }
+
+    pub async fn replace(
+        &self,
+        board_repository: &BoardRepository,
+        column_states: Vec<(i64, String)>,
+    ) -> Result<Vec<ColumnState>, GITrelloError>
+    {
+        let mut column_ids = HashSet::new();
+        for (column_id, state) in column_states.iter() {
+            if !STATES.contains(&state.as_str()) {
+                return Err(GITrelloError::BadRequest {
+                    message: format!("{} is not a valid state, expected one of {}", state, STATES.join(", ")),
+                });
+            }
+
+            if !column_ids.insert(*column_id) {
+                return Err(GITrelloError::BadRequest {
+                    message: format!("Column {} is mapped more than once", column_id),
+                });
+            }
+        }
+
+        let data = column_states
+            .into_iter()
+            .map(|(column_id, state)| NewColumnState {
+                board_repository_id: board_repository.id,
+                column_id,
+                state,
+            })
+            .collect();
+
+        self.actor
+            .send(ReplaceColumnStatesMessage { board_repository_id: board_repository.id, data })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })?
+    }
+}
+
+#[test]
+fn test_get_issue_state() {
+    use crate::models::column_state::{STATE_CLOSED, STATE_OPEN};
+
+    let column_states = vec![
+        ColumnState { id: 1, board_repository_id: 1, column_id: 10, state: String::from(STATE_OPEN) },
+        ColumnState { id: 2, board_repository_id: 1, column_id: 20, state: String::from(STATE_CLOSED) },
+    ];
+
+    assert_eq!(get_issue_state(&column_states, 10), Some(STATE_OPEN));
+    assert_eq!(get_issue_state(&column_states, 20), Some(STATE_CLOSED));
+    assert_eq!(get_issue_state(&column_states, 30), None);
+}
@@ -0,0 +1,809 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+use actix::{Actor, Context, Handler};
+use async_trait::async_trait;
+use diesel::r2d2::ConnectionManager;
+use diesel::PgConnection;
+
+use crate::errors::GITrelloError;
+use crate::models::board_repository::BoardRepository;
+use crate::models::github_webhook::GithubWebhook;
+use crate::models::issue_ticket::IssueTicket;
+use crate::services::github_api_cache::GithubAPICache;
+use crate::services::github_api_client::{GitHubAPI, GitHubAPIClientFactory};
+use crate::services::gitrello_api_client::{GITrelloAPI, GITrelloAPIClientFactory};
+use crate::services::repositories::github_webhook::{
+    CreateGithubWebhookMessage, GetBoardRepositoriesByWebhookIdMessage, GetByBoardRepositoryIdMessage,
+    GetByRepositoryNameAndOwnerMessage, UpdateWebhookIdMessage,
+};
+use crate::services::repositories::issue_ticket::{
+    CreateIssueTicketMessage, DeleteIssueTicketMessage, GetIssueTicketsByBoardRepositoryIdsAndIssueIdMessage,
+    GetIssueTicketsByBoardRepositoryIdsAndIssueNumberMessage, GetIssueTicketsByTicketIdMessage,
+    UpdateIssueTicketTicketIdMessage,
+};
+use crate::state::{Environment, State};
+use crate::value_objects::github_api::{GithubUser, Issue, Repository, Webhook, WebhookConfig};
+use crate::value_objects::gitrello_api::{Permissions, Ticket, TicketComment};
+
+pub const FAKE_WEBHOOK_URL: &str = "https://gitrello.me/github/api/v1/webhook";
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GitHubAPICall {
+    GetUser,
+    GetRepositories { affiliation: String, visibility: String },
+    GetRepository { repository: String },
+    GetOpenIssues { repository: String, page: i32 },
+    CreateIssue { repository: String, title: String },
+    UpdateIssueTitle { repository: String, issue_number: i32, title: String },
+    CloseIssue { repository: String, issue_number: i32 },
+    ReopenIssue { repository: String, issue_number: i32 },
+    CreateWebhook { repository: String, events: Vec<String> },
+    GetWebhooks { repository: String },
+    UpdateWebhookEvents { repository: String, webhook_id: i64, events: Vec<String> },
+    UpdateWebhookConfig { repository: String, webhook_id: i64, url: String },
+    DeleteWebhook { repository: String, webhook_id: i64 },
+}
+
+impl GitHubAPICall {
+    fn get_method(&self) -> &'static str {
+        match self {
+            Self::GetUser => "get_user",
+            Self::GetRepositories { .. } => "get_repositories",
+            Self::GetRepository { .. } => "get_repository",
+            Self::GetOpenIssues { .. } => "get_open_issues",
+            Self::CreateIssue { .. } => "create_issue",
+            Self::UpdateIssueTitle { .. } => "update_issue_title",
+            Self::CloseIssue { .. } => "close_issue",
+            Self::ReopenIssue { .. } => "reopen_issue",
+            Self::CreateWebhook { .. } => "create_webhook",
+            Self::GetWebhooks { .. } => "get_webhooks",
+            Self::UpdateWebhookEvents { .. } => "update_webhook_events",
+            Self::UpdateWebhookConfig { .. } => "update_webhook_config",
+            Self::DeleteWebhook { .. } => "delete_webhook",
+        }
+    }
+}
+
+struct FakeWebhook {
+    id: i64,
+    repository: String,
+    url: String,
+    events: Vec<String>,
+}
+
+impl FakeWebhook {
+    fn to_webhook(&self) -> Webhook {
+        Webhook {
+            id: self.id,
+            config: WebhookConfig { url: Some(self.url.clone()) },
+            events: self.events.clone(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct FakeGitHub {
+    calls: Vec<GitHubAPICall>,
+    failing_methods: HashSet<&'static str>,
+    webhooks: Vec<FakeWebhook>,
+    last_id: i64,
+}
+
+// Keeps GitHub in memory, clones share it so tests can look at what services did
+#[derive(Clone, Default)]
+pub struct FakeGitHubAPIClient {
+    github: Arc<Mutex<FakeGitHub>>,
+}
+
+impl FakeGitHubAPIClient {
+    pub fn add_webhook(&self, repository_name: &str, repository_owner: &str, events: Vec<String>) -> i64 {
+        let mut github = self.github.lock().unwrap();
+        github.last_id += 1;
+        let id = github.last_id;
+        github.webhooks.push(FakeWebhook {
+            id,
+            repository: format!("{}/{}", repository_owner, repository_name),
+            url: String::from(FAKE_WEBHOOK_URL),
+            events,
+        });
+        id
+    }
+
+    // Every following call of the method fails the way GitHub API errors do
+    pub fn fail(&self, method: &'static str) {
+        self.github.lock().unwrap().failing_methods.insert(method);
+    }
+
+    pub fn get_calls(&self) -> Vec<GitHubAPICall> {
+        self.github.lock().unwrap().calls.clone()
+    }
+
+    pub fn get_webhook_ids(&self, repository_name: &str, repository_owner: &str) -> Vec<i64> {
+        let repository = format!("{}/{}", repository_owner, repository_name);
+        self.github
+            .lock()
+            .unwrap()
+            .webhooks
+            .iter()
+            .filter(|webhook| webhook.repository == repository)
+            .map(|webhook| webhook.id)
+            .collect()
+    }
+
+    fn call(&self, call: GitHubAPICall) -> Result<(), GITrelloError> {
+        let mut github = self.github.lock().unwrap();
+        let method = call.get_method();
+        github.calls.push(call);
+
+        match github.failing_methods.contains(method) {
+            true => Err(GITrelloError::GitHubAPIClientError { message: format!("{} failed", method) }),
+            false => Ok(()),
+        }
+    }
+
+    fn next_id(&self) -> i64 {
+        let mut github = self.github.lock().unwrap();
+        github.last_id += 1;
+        github.last_id
+    }
+
+    fn get_issue(&self, repository: &str, number: i32, title: &str) -> Issue {
+        Issue {
+            id: self.next_id(),
+            number,
+            html_url: format!("https://github.com/{}/issues/{}", repository, number),
+            title: title.to_string(),
+            body: None,
+            user: get_fake_github_user(),
+            labels: vec![],
+            pull_request: None,
+        }
+    }
+}
+
+fn get_fake_github_user() -> GithubUser {
+    GithubUser { id: 1, login: String::from("gitrello"), user_type: String::from("User") }
+}
+
+fn get_not_found_error() -> GITrelloError {
+    GITrelloError::GitHubAPIClientError { message: String::from("Not Found") }
+}
+
+#[async_trait(?Send)]
+impl GitHubAPI for FakeGitHubAPIClient {
+    async fn get_user(&self) -> Result<GithubUser, GITrelloError> {
+        self.call(GitHubAPICall::GetUser)?;
+        Ok(get_fake_github_user())
+    }
+
+    async fn get_repositories(
+        &self,
+        affiliation: &str,
+        visibility: &str,
+    ) -> Result<(Vec<Repository>, bool), GITrelloError>
+    {
+        self.call(GitHubAPICall::GetRepositories {
+            affiliation: affiliation.to_string(),
+            visibility: visibility.to_string(),
+        })?;
+        Ok((vec![], false))
+    }
+
+    async fn get_repository(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+    ) -> Result<Repository, GITrelloError>
+    {
+        let repository = format!("{}/{}", repository_owner, repository_name);
+        self.call(GitHubAPICall::GetRepository { repository: repository.clone() })?;
+        Ok(Repository { id: self.next_id(), full_name: repository, default_branch: Some(String::from("main")) })
+    }
+
+    async fn get_open_issues(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+        page: i32,
+    ) -> Result<Vec<Issue>, GITrelloError>
+    {
+        self.call(GitHubAPICall::GetOpenIssues {
+            repository: format!("{}/{}", repository_owner, repository_name),
+            page,
+        })?;
+        Ok(vec![])
+    }
+
+    async fn create_issue(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+        title: &str,
+        _body: &str,
+    ) -> Result<Issue, GITrelloError>
+    {
+        let repository = format!("{}/{}", repository_owner, repository_name);
+        self.call(GitHubAPICall::CreateIssue { repository: repository.clone(), title: title.to_string() })?;
+
+        let number = self.next_id() as i32;
+        Ok(self.get_issue(repository.as_str(), number, title))
+    }
+
+    async fn update_issue_title(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+        issue_number: i32,
+        title: &str,
+    ) -> Result<Issue, GITrelloError>
+    {
+        let repository = format!("{}/{}", repository_owner, repository_name);
+        self.call(GitHubAPICall::UpdateIssueTitle {
+            repository: repository.clone(),
+            issue_number,
+            title: title.to_string(),
+        })?;
+        Ok(self.get_issue(repository.as_str(), issue_number, title))
+    }
+
+    async fn close_issue(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+        issue_number: i32,
+    ) -> Result<Issue, GITrelloError>
+    {
+        let repository = format!("{}/{}", repository_owner, repository_name);
+        self.call(GitHubAPICall::CloseIssue { repository: repository.clone(), issue_number })?;
+        Ok(self.get_issue(repository.as_str(), issue_number, ""))
+    }
+
+    async fn reopen_issue(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+        issue_number: i32,
+    ) -> Result<Issue, GITrelloError>
+    {
+        let repository = format!("{}/{}", repository_owner, repository_name);
+        self.call(GitHubAPICall::ReopenIssue { repository: repository.clone(), issue_number })?;
+        Ok(self.get_issue(repository.as_str(), issue_number, ""))
+    }
+
+    async fn create_webhook(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+        webhook_url: &str,
+        _secret: &str,
+        events: Vec<String>,
+    ) -> Result<Webhook, GITrelloError>
+    {
+        let repository = format!("{}/{}", repository_owner, repository_name);
+        self.call(GitHubAPICall::CreateWebhook { repository: repository.clone(), events: events.clone() })?;
+
+        let webhook = FakeWebhook { id: self.next_id(), repository, url: webhook_url.to_string(), events };
+        let result = webhook.to_webhook();
+        self.github.lock().unwrap().webhooks.push(webhook);
+        Ok(result)
+    }
+
+    async fn get_webhooks(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+    ) -> Result<Vec<Webhook>, GITrelloError>
+    {
+        let repository = format!("{}/{}", repository_owner, repository_name);
+        self.call(GitHubAPICall::GetWebhooks { repository: repository.clone() })?;
+
+        let github = self.github.lock().unwrap();
+        let webhooks = github.webhooks
+            .iter()
+            .filter(|webhook| webhook.repository == repository)
+            .map(FakeWebhook::to_webhook)
+            .collect();
+        Ok(webhooks)
+    }
+
+    async fn update_webhook_events(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+        webhook_id: i64,
+        events: Vec<String>,
+    ) -> Result<Webhook, GITrelloError>
+    {
+        let repository = format!("{}/{}", repository_owner, repository_name);
+        self.call(GitHubAPICall::UpdateWebhookEvents {
+            repository: repository.clone(),
+            webhook_id,
+            events: events.clone(),
+        })?;
+
+        let mut github = self.github.lock().unwrap();
+        let webhook = github.webhooks
+            .iter_mut()
+            .find(|webhook| webhook.id == webhook_id && webhook.repository == repository)
+            .ok_or_else(get_not_found_error)?;
+        webhook.events = events;
+        Ok(webhook.to_webhook())
+    }
+
+    async fn update_webhook_config(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+        webhook_id: i64,
+        webhook_url: &str,
+        _secret: &str,
+    ) -> Result<WebhookConfig, GITrelloError>
+    {
+        let repository = format!("{}/{}", repository_owner, repository_name);
+        self.call(GitHubAPICall::UpdateWebhookConfig {
+            repository: repository.clone(),
+            webhook_id,
+            url: webhook_url.to_string(),
+        })?;
+
+        let mut github = self.github.lock().unwrap();
+        let webhook = github.webhooks
+            .iter_mut()
+            .find(|webhook| webhook.id == webhook_id && webhook.repository == repository)
+            .ok_or_else(get_not_found_error)?;
+        webhook.url = webhook_url.to_string();
+        Ok(WebhookConfig { url: Some(webhook.url.clone()) })
+    }
+
+    async fn delete_webhook(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+        webhook_id: i64,
+    ) -> Result<bool, GITrelloError>
+    {
+        let repository = format!("{}/{}", repository_owner, repository_name);
+        self.call(GitHubAPICall::DeleteWebhook { repository: repository.clone(), webhook_id })?;
+
+        let mut github = self.github.lock().unwrap();
+        let index = github.webhooks
+            .iter()
+            .position(|webhook| webhook.id == webhook_id && webhook.repository == repository)
+            .ok_or_else(get_not_found_error)?;
+        github.webhooks.remove(index);
+        Ok(true)
+    }
+}
+
+// Hands out the same fake GitHub whatever the token is
+pub struct FakeGitHubAPIClientFactory {
+    pub client: FakeGitHubAPIClient,
+}
+
+impl GitHubAPIClientFactory for FakeGitHubAPIClientFactory {
+    fn create(&self, _state: &State, _api_url: &str, _access_token: &str) -> Box<dyn GitHubAPI> {
+        Box::new(self.client.clone())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GITrelloAPICall {
+    GetBoardPermissions { user_id: i64, board_id: i64 },
+    CreateTicket { board_id: i64, title: String, body: String, idempotency_key: Option<String> },
+    UpdateTicket { ticket_id: i64, title: String, body: String },
+    CloseTicket { ticket_id: i64 },
+    ReopenTicket { ticket_id: i64 },
+    ArchiveTicket { ticket_id: i64 },
+    CreateTicketComment { ticket_id: i64, body: String },
+    UpdateTicketComment { ticket_id: i64, ticket_comment_id: i64, body: String },
+    DeleteTicketComment { ticket_id: i64, ticket_comment_id: i64 },
+}
+
+impl GITrelloAPICall {
+    fn get_method(&self) -> &'static str {
+        match self {
+            Self::GetBoardPermissions { .. } => "get_board_permissions",
+            Self::CreateTicket { .. } => "create_ticket",
+            Self::UpdateTicket { .. } => "update_ticket",
+            Self::CloseTicket { .. } => "close_ticket",
+            Self::ReopenTicket { .. } => "reopen_ticket",
+            Self::ArchiveTicket { .. } => "archive_ticket",
+            Self::CreateTicketComment { .. } => "create_ticket_comment",
+            Self::UpdateTicketComment { .. } => "update_ticket_comment",
+            Self::DeleteTicketComment { .. } => "delete_ticket_comment",
+        }
+    }
+}
+
+#[derive(Default)]
+struct FakeGITrello {
+    calls: Vec<GITrelloAPICall>,
+    failing_methods: HashSet<&'static str>,
+    last_id: i64,
+}
+
+#[derive(Clone, Default)]
+pub struct FakeGITrelloAPIClient {
+    gitrello: Arc<Mutex<FakeGITrello>>,
+}
+
+impl FakeGITrelloAPIClient {
+    pub fn fail(&self, method: &'static str) {
+        self.gitrello.lock().unwrap().failing_methods.insert(method);
+    }
+
+    pub fn get_calls(&self) -> Vec<GITrelloAPICall> {
+        self.gitrello.lock().unwrap().calls.clone()
+    }
+
+    fn call(&self, call: GITrelloAPICall) -> Result<i64, GITrelloError> {
+        let mut gitrello = self.gitrello.lock().unwrap();
+        let method = call.get_method();
+        gitrello.calls.push(call);
+
+        if gitrello.failing_methods.contains(method) {
+            return Err(GITrelloError::GITrelloAPIClientError {
+                message: format!("{} failed", method),
+                status_code: 503,
+            });
+        }
+
+        gitrello.last_id += 1;
+        Ok(gitrello.last_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl GITrelloAPI for FakeGITrelloAPIClient {
+    async fn get_board_permissions(&self, user_id: i64, board_id: i64) -> Result<Permissions, GITrelloError> {
+        self.call(GITrelloAPICall::GetBoardPermissions { user_id, board_id })?;
+        Ok(Permissions { can_read: true, can_mutate: true, can_delete: true })
+    }
+
+    async fn create_ticket(
+        &self,
+        board_id: i64,
+        title: &str,
+        body: &str,
+        idempotency_key: Option<&str>,
+    ) -> Result<Ticket, GITrelloError>
+    {
+        let id = self.call(GITrelloAPICall::CreateTicket {
+            board_id,
+            title: title.to_string(),
+            body: body.to_string(),
+            idempotency_key: idempotency_key.map(String::from),
+        })?;
+        Ok(Ticket { id })
+    }
+
+    async fn update_ticket(&self, ticket_id: i64, title: &str, body: &str) -> Result<Ticket, GITrelloError> {
+        self.call(GITrelloAPICall::UpdateTicket { ticket_id, title: title.to_string(), body: body.to_string() })?;
+        Ok(Ticket { id: ticket_id })
+    }
+
+    async fn close_ticket(&self, ticket_id: i64) -> Result<Ticket, GITrelloError> {
+        self.call(GITrelloAPICall::CloseTicket { ticket_id })?;
+        Ok(Ticket { id: ticket_id })
+    }
+
+    async fn reopen_ticket(&self, ticket_id: i64) -> Result<Ticket, GITrelloError> {
+        self.call(GITrelloAPICall::ReopenTicket { ticket_id })?;
+        Ok(Ticket { id: ticket_id })
+    }
+
+    async fn archive_ticket(&self, ticket_id: i64) -> Result<Ticket, GITrelloError> {
+        self.call(GITrelloAPICall::ArchiveTicket { ticket_id })?;
+        Ok(Ticket { id: ticket_id })
+    }
+
+    async fn create_ticket_comment(&self, ticket_id: i64, body: &str) -> Result<TicketComment, GITrelloError> {
+        let id = self.call(GITrelloAPICall::CreateTicketComment { ticket_id, body: body.to_string() })?;
+        Ok(TicketComment { id })
+    }
+
+    async fn update_ticket_comment(
+        &self,
+        ticket_id: i64,
+        ticket_comment_id: i64,
+        body: &str,
+    ) -> Result<TicketComment, GITrelloError>
+    {
+        self.call(GITrelloAPICall::UpdateTicketComment { ticket_id, ticket_comment_id, body: body.to_string() })?;
+        Ok(TicketComment { id: ticket_comment_id })
+    }
+
+    async fn delete_ticket_comment(&self, ticket_id: i64, ticket_comment_id: i64) -> Result<(), GITrelloError> {
+        self.call(GITrelloAPICall::DeleteTicketComment { ticket_id, ticket_comment_id })?;
+        Ok(())
+    }
+}
+
+pub struct FakeGITrelloAPIClientFactory {
+    pub client: FakeGITrelloAPIClient,
+}
+
+impl GITrelloAPIClientFactory for FakeGITrelloAPIClientFactory {
+    fn create(&self, _state: &State, _access_token: Option<&str>) -> Box<dyn GITrelloAPI> {
+        Box::new(self.client.clone())
+    }
+}
+
+// The pool never connects, services under test get their storage from fake repositories
+pub fn get_fake_state(github: &FakeGitHubAPIClient, gitrello: &FakeGITrelloAPIClient) -> State {
+    let manager = ConnectionManager::<PgConnection>::new("postgres://127.0.0.1:5432/unused");
+    let db_pool = r2d2::Pool::builder()
+        .min_idle(Some(0))
+        .build_unchecked(manager);
+
+    State {
+        environment: Environment::Test,
+        db_pool,
+        secret: String::from("secret"),
+        gitrello_host: String::from("https://gitrello.me"),
+        gitrello_url: String::from("https://gitrello.me"),
+        gitrello_access_token: String::from("gitrello-access-token"),
+        webhook_url: String::from(FAKE_WEBHOOK_URL),
+        admin_token: String::from("admin-token"),
+        github_api_url: String::from("https://api.github.com"),
+        github_enterprise_hosts: vec![String::from("github.example.com")],
+        github_api_cache: GithubAPICache::default(),
+        http_client: reqwest::Client::new(),
+        github_api_client_factory: Arc::new(FakeGitHubAPIClientFactory { client: github.clone() }),
+        gitrello_api_client_factory: Arc::new(FakeGITrelloAPIClientFactory { client: gitrello.clone() }),
+    }
+}
+
+pub fn get_fake_board_repository(id: i32, repository_name: &str, events: &[&str]) -> BoardRepository {
+    BoardRepository {
+        id,
+        github_profile_id: 1,
+        board_id: id as i64,
+        repository_name: String::from(repository_name),
+        repository_owner: String::from("gitrello"),
+        sync_pull_requests: false,
+        allowed_bot_logins: vec![],
+        repository_id: None,
+        is_read_only: false,
+        events: events.iter().map(|event| event.to_string()).collect(),
+        ticket_title_template: None,
+        ticket_body_template: None,
+    }
+}
+
+pub fn get_fake_github_webhook(board_repository_id: i32, webhook_id: i64) -> GithubWebhook {
+    GithubWebhook {
+        id: board_repository_id,
+        webhook_id,
+        url: String::from(FAKE_WEBHOOK_URL),
+        board_repository_id,
+        secret: format!("secret-{}", webhook_id),
+        last_ping_at: None,
+        last_delivery_at: None,
+        last_error: None,
+    }
+}
+
+// Stores github_webhook rows joined to board_repository rows the way Postgres would
+#[derive(Default)]
+pub struct FakeGithubWebhookRepository {
+    pub board_repositories: Vec<BoardRepository>,
+    pub github_webhooks: Vec<GithubWebhook>,
+}
+
+impl FakeGithubWebhookRepository {
+    pub fn add_github_webhook(&mut self, board_repository_id: i32, webhook_id: i64) {
+        self.github_webhooks.push(GithubWebhook {
+            id: self.github_webhooks.len() as i32 + 1,
+            ..get_fake_github_webhook(board_repository_id, webhook_id)
+        });
+    }
+
+    fn get_board_repository(&self, id: i32) -> Option<&BoardRepository> {
+        self.board_repositories.iter().find(|board_repository| board_repository.id == id)
+    }
+}
+
+impl Actor for FakeGithubWebhookRepository {
+    type Context = Context<Self>;
+}
+
+impl Handler<CreateGithubWebhookMessage> for FakeGithubWebhookRepository {
+    type Result = Result<GithubWebhook, GITrelloError>;
+
+    fn handle(&mut self, msg: CreateGithubWebhookMessage, _: &mut Self::Context) -> Self::Result {
+        let github_webhook = GithubWebhook {
+            id: self.github_webhooks.len() as i32 + 1,
+            webhook_id: msg.data.webhook_id,
+            url: msg.data.url,
+            board_repository_id: msg.data.board_repository_id,
+            secret: msg.data.secret,
+            last_ping_at: None,
+            last_delivery_at: None,
+            last_error: None,
+        };
+        self.github_webhooks.push(github_webhook.clone());
+        Ok(github_webhook)
+    }
+}
+
+impl Handler<UpdateWebhookIdMessage> for FakeGithubWebhookRepository {
+    type Result = Result<GithubWebhook, GITrelloError>;
+
+    fn handle(&mut self, msg: UpdateWebhookIdMessage, _: &mut Self::Context) -> Self::Result {
+        let github_webhook = self.github_webhooks
+            .iter_mut()
+            .find(|github_webhook| github_webhook.id == msg.github_webhook.id)
+            .ok_or_else(|| GITrelloError::NotFound {
+                message: format!("github_webhook {} does not exist", msg.github_webhook.id),
+            })?;
+
+        github_webhook.webhook_id = msg.webhook_id;
+        github_webhook.url = msg.url;
+        github_webhook.secret = msg.secret;
+        Ok(github_webhook.clone())
+    }
+}
+
+impl Handler<GetByBoardRepositoryIdMessage> for FakeGithubWebhookRepository {
+    type Result = Result<GithubWebhook, GITrelloError>;
+
+    fn handle(&mut self, msg: GetByBoardRepositoryIdMessage, _: &mut Self::Context) -> Self::Result {
+        self.github_webhooks
+            .iter()
+            .find(|github_webhook| github_webhook.board_repository_id == msg.board_repository_id)
+            .cloned()
+            .ok_or_else(|| GITrelloError::NotFound {
+                message: format!("github_webhook for board_repository {} does not exist", msg.board_repository_id),
+            })
+    }
+}
+
+impl Handler<GetByRepositoryNameAndOwnerMessage> for FakeGithubWebhookRepository {
+    type Result = Result<Vec<GithubWebhook>, GITrelloError>;
+
+    fn handle(&mut self, msg: GetByRepositoryNameAndOwnerMessage, _: &mut Self::Context) -> Self::Result {
+        let github_webhooks = self.github_webhooks
+            .iter()
+            .filter(|github_webhook| {
+                self.get_board_repository(github_webhook.board_repository_id)
+                    .map(|board_repository| {
+                        board_repository.repository_name == msg.repository_name &&
+                            board_repository.repository_owner == msg.repository_owner
+                    })
+                    .unwrap_or(false)
+            })
+            .cloned()
+            .collect();
+        Ok(github_webhooks)
+    }
+}
+
+impl Handler<GetBoardRepositoriesByWebhookIdMessage> for FakeGithubWebhookRepository {
+    type Result = Result<Vec<BoardRepository>, GITrelloError>;
+
+    fn handle(&mut self, msg: GetBoardRepositoriesByWebhookIdMessage, _: &mut Self::Context) -> Self::Result {
+        let board_repositories = self.github_webhooks
+            .iter()
+            .filter(|github_webhook| github_webhook.webhook_id == msg.webhook_id)
+            .filter_map(|github_webhook| self.get_board_repository(github_webhook.board_repository_id))
+            .cloned()
+            .collect();
+        Ok(board_repositories)
+    }
+}
+
+// Enforces uq_issue_ticket the way Postgres would
+#[derive(Default)]
+pub struct FakeIssueTicketRepository {
+    pub issue_tickets: Vec<IssueTicket>,
+    last_id: i32,
+}
+
+impl FakeIssueTicketRepository {
+    pub fn add_issue_ticket(&mut self, board_repository_id: i32, issue_id: i64, issue_number: i32, ticket_id: i64) {
+        self.last_id += 1;
+        self.issue_tickets.push(IssueTicket {
+            id: self.last_id,
+            board_repository_id,
+            issue_id,
+            issue_number,
+            ticket_id,
+        });
+    }
+
+    fn filter<P>(&self, predicate: P) -> Vec<IssueTicket>
+        where P: Fn(&IssueTicket) -> bool
+    {
+        self.issue_tickets.iter().filter(|issue_ticket| predicate(issue_ticket)).cloned().collect()
+    }
+}
+
+impl Actor for FakeIssueTicketRepository {
+    type Context = Context<Self>;
+}
+
+impl Handler<CreateIssueTicketMessage> for FakeIssueTicketRepository {
+    type Result = Result<IssueTicket, GITrelloError>;
+
+    fn handle(&mut self, msg: CreateIssueTicketMessage, _: &mut Self::Context) -> Self::Result {
+        let data = msg.data;
+        let exists = self.issue_tickets.iter().any(|issue_ticket| {
+            issue_ticket.board_repository_id == data.board_repository_id && issue_ticket.issue_id == data.issue_id
+        });
+        if exists {
+            return Err(GITrelloError::AlreadyExists {
+                message: String::from("duplicate key value violates unique constraint \"uq_issue_ticket\""),
+            });
+        }
+
+        self.add_issue_ticket(data.board_repository_id, data.issue_id, data.issue_number, data.ticket_id);
+        Ok(self.issue_tickets.last().cloned().expect("was just added"))
+    }
+}
+
+impl Handler<GetIssueTicketsByBoardRepositoryIdsAndIssueIdMessage> for FakeIssueTicketRepository {
+    type Result = Result<Vec<IssueTicket>, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: GetIssueTicketsByBoardRepositoryIdsAndIssueIdMessage,
+        _: &mut Self::Context,
+    ) -> Self::Result
+    {
+        Ok(self.filter(|issue_ticket| {
+            msg.board_repository_ids.contains(&issue_ticket.board_repository_id) &&
+                issue_ticket.issue_id == msg.issue_id
+        }))
+    }
+}
+
+impl Handler<GetIssueTicketsByBoardRepositoryIdsAndIssueNumberMessage> for FakeIssueTicketRepository {
+    type Result = Result<Vec<IssueTicket>, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: GetIssueTicketsByBoardRepositoryIdsAndIssueNumberMessage,
+        _: &mut Self::Context,
+    ) -> Self::Result
+    {
+        Ok(self.filter(|issue_ticket| {
+            msg.board_repository_ids.contains(&issue_ticket.board_repository_id) &&
+                issue_ticket.issue_number == msg.issue_number
+        }))
+    }
+}
+
+impl Handler<GetIssueTicketsByTicketIdMessage> for FakeIssueTicketRepository {
+    type Result = Result<Vec<IssueTicket>, GITrelloError>;
+
+    fn handle(&mut self, msg: GetIssueTicketsByTicketIdMessage, _: &mut Self::Context) -> Self::Result {
+        Ok(self.filter(|issue_ticket| issue_ticket.ticket_id == msg.ticket_id))
+    }
+}
+
+impl Handler<UpdateIssueTicketTicketIdMessage> for FakeIssueTicketRepository {
+    type Result = Result<IssueTicket, GITrelloError>;
+
+    fn handle(&mut self, msg: UpdateIssueTicketTicketIdMessage, _: &mut Self::Context) -> Self::Result {
+        let issue_ticket = self.issue_tickets
+            .iter_mut()
+            .find(|issue_ticket| issue_ticket.id == msg.issue_ticket.id)
+            .ok_or_else(|| GITrelloError::NotFound {
+                message: format!("issue_ticket {} does not exist", msg.issue_ticket.id),
+            })?;
+
+        issue_ticket.ticket_id = msg.ticket_id;
+        Ok(issue_ticket.clone())
+    }
+}
+
+impl Handler<DeleteIssueTicketMessage> for FakeIssueTicketRepository {
+    type Result = Result<(), GITrelloError>;
+
+    fn handle(&mut self, msg: DeleteIssueTicketMessage, _: &mut Self::Context) -> Self::Result {
+        self.issue_tickets.retain(|issue_ticket| issue_ticket.id != msg.id);
+        Ok(())
+    }
+}
@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+use sha2::{Digest, Sha256};
+
+const RATE_LIMIT_REMAINING_HEADER: &str = "x-ratelimit-remaining";
+const RATE_LIMIT_RESET_HEADER: &str = "x-ratelimit-reset";
+// GitHub asks to wait at least a minute after hitting a secondary rate limit without Retry-After
+const SECONDARY_RATE_LIMIT_WAIT_SECONDS: i64 = 60;
+// The least recently used response is dropped to make room for a new one
+const MAX_CACHED_RESPONSES: usize = 1000;
+
+#[derive(Clone)]
+pub struct CachedResponse {
+    pub etag: String,
+    pub body: String,
+    pub link: Option<String>,
+}
+
+#[derive(Default)]
+struct CachedResponses {
+    // Responses along with the moment they were last used, counted in cache accesses
+    entries: HashMap<String, (CachedResponse, u64)>,
+    accesses: u64,
+}
+
+// Shared by all GitHub API clients, rate limits and ETags belong to access tokens rather than to clients
+#[derive(Clone, Default)]
+pub struct GithubAPICache {
+    blocked_until: Arc<Mutex<HashMap<String, DateTime<Utc>>>>,
+    responses: Arc<Mutex<CachedResponses>>,
+}
+
+impl GithubAPICache {
+    // Tokens are not kept in memory as they are
+    pub fn get_token_key(access_token: &str) -> String {
+        hex::encode(Sha256::digest(access_token.as_bytes()))
+    }
+
+    pub fn get_blocked_until(&self, token_key: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut blocked_until = self.blocked_until.lock().expect("lock should not be poisoned");
+
+        match blocked_until.get(token_key) {
+            Some(until) if *until > now => Some(*until),
+            Some(_) => {
+                blocked_until.remove(token_key);
+                None
+            },
+            None => None,
+        }
+    }
+
+    pub fn block_until(&self, token_key: &str, until: DateTime<Utc>) {
+        self.blocked_until
+            .lock()
+            .expect("lock should not be poisoned")
+            .insert(token_key.to_string(), until);
+    }
+
+    pub fn get_response(&self, token_key: &str, url: &str) -> Option<CachedResponse> {
+        let mut responses = self.responses.lock().expect("lock should not be poisoned");
+        responses.accesses += 1;
+        let accesses = responses.accesses;
+
+        responses.entries
+            .get_mut(&get_response_key(token_key, url))
+            .map(|(response, last_used)| {
+                *last_used = accesses;
+                response.clone()
+            })
+    }
+
+    pub fn set_response(&self, token_key: &str, url: &str, response: CachedResponse) {
+        let mut responses = self.responses.lock().expect("lock should not be poisoned");
+        responses.accesses += 1;
+        let accesses = responses.accesses;
+
+        let key = get_response_key(token_key, url);
+        if responses.entries.len() >= MAX_CACHED_RESPONSES && !responses.entries.contains_key(&key) {
+            let least_recently_used_key = responses.entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(key, _)| key.clone());
+
+            if let Some(least_recently_used_key) = least_recently_used_key {
+                responses.entries.remove(&least_recently_used_key);
+            }
+        }
+        responses.entries.insert(key, (response, accesses));
+    }
+}
+
+fn get_response_key(token_key: &str, url: &str) -> String {
+    format!("{} {}", token_key, url)
+}
+
+fn get_header_i64(headers: &HeaderMap, name: &str) -> Option<i64> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<i64>().ok())
+}
+
+// Returns the moment requests with the token can be made again, when the limit is exhausted.
+// Primary limits report the reset time, secondary ones Retry-After or only the message.
+pub fn get_rate_limit_reset(
+    status: StatusCode,
+    headers: &HeaderMap,
+    message: &str,
+    now: DateTime<Utc>,
+) -> Option<DateTime<Utc>>
+{
+    if let Some(retry_after) = get_header_i64(headers, RETRY_AFTER.as_str()) {
+        if status == StatusCode::FORBIDDEN || status == StatusCode::TOO_MANY_REQUESTS {
+            return Some(now + Duration::seconds(retry_after));
+        }
+    }
+
+    if get_header_i64(headers, RATE_LIMIT_REMAINING_HEADER) == Some(0) {
+        let reset = get_header_i64(headers, RATE_LIMIT_RESET_HEADER)
+            .and_then(|reset| Utc.timestamp_opt(reset, 0).single())
+            .unwrap_or(now + Duration::seconds(SECONDARY_RATE_LIMIT_WAIT_SECONDS));
+        return Some(reset);
+    }
+
+    let is_secondary_rate_limit = message.to_lowercase().contains("secondary rate limit");
+    if (status == StatusCode::FORBIDDEN || status == StatusCode::TOO_MANY_REQUESTS) && is_secondary_rate_limit {
+        return Some(now + Duration::seconds(SECONDARY_RATE_LIMIT_WAIT_SECONDS));
+    }
+
+    None
+}
+
+#[cfg(test)]
+fn get_test_headers(headers: &[(&'static str, &str)]) -> HeaderMap {
+    use reqwest::header::HeaderValue;
+
+    let mut header_map = HeaderMap::new();
+    for (name, value) in headers {
+        header_map.insert(*name, HeaderValue::from_str(value).unwrap());
+    }
+    header_map
+}
+
+#[test]
+fn test_get_rate_limit_reset_primary() {
+    let now = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+
+    let headers = get_test_headers(&[(RATE_LIMIT_REMAINING_HEADER, "0"), (RATE_LIMIT_RESET_HEADER, "1700000600")]);
+    assert_eq!(
+        get_rate_limit_reset(StatusCode::FORBIDDEN, &headers, "API rate limit exceeded", now),
+        Some(Utc.timestamp_opt(1_700_000_600, 0).unwrap()),
+    );
+    // The last request allowed by the limit succeeds, the next one would not
+    assert_eq!(
+        get_rate_limit_reset(StatusCode::OK, &headers, "", now),
+        Some(Utc.timestamp_opt(1_700_000_600, 0).unwrap()),
+    );
+
+    let headers = get_test_headers(&[(RATE_LIMIT_REMAINING_HEADER, "42"), (RATE_LIMIT_RESET_HEADER, "1700000600")]);
+    assert_eq!(get_rate_limit_reset(StatusCode::OK, &headers, "", now), None);
+    assert_eq!(get_rate_limit_reset(StatusCode::FORBIDDEN, &headers, "Resource not accessible", now), None);
+}
+
+#[test]
+fn test_get_rate_limit_reset_secondary() {
+    let now = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+
+    let headers = get_test_headers(&[("retry-after", "30"), (RATE_LIMIT_REMAINING_HEADER, "4000")]);
+    assert_eq!(
+        get_rate_limit_reset(StatusCode::FORBIDDEN, &headers, "", now),
+        Some(now + Duration::seconds(30)),
+    );
+
+    let message = "You have exceeded a secondary rate limit. Please wait a few minutes before you try again.";
+    assert_eq!(
+        get_rate_limit_reset(StatusCode::FORBIDDEN, &HeaderMap::new(), message, now),
+        Some(now + Duration::seconds(SECONDARY_RATE_LIMIT_WAIT_SECONDS)),
+    );
+}
+
+#[test]
+fn test_github_api_cache_blocked_until() {
+    let cache = GithubAPICache::default();
+    let now = Utc::now();
+    let token_key = GithubAPICache::get_token_key("token");
+
+    assert_eq!(cache.get_blocked_until(&token_key, now), None);
+
+    cache.block_until(&token_key, now + Duration::seconds(10));
+    assert_eq!(cache.get_blocked_until(&token_key, now), Some(now + Duration::seconds(10)));
+    assert_eq!(cache.get_blocked_until(&GithubAPICache::get_token_key("other token"), now), None);
+    assert_eq!(cache.get_blocked_until(&token_key, now + Duration::seconds(11)), None);
+}
+
+#[test]
+fn test_github_api_cache_evicts_least_recently_used_response() {
+    let cache = GithubAPICache::default();
+    let token_key = GithubAPICache::get_token_key("token");
+    let get_response = |etag: &str| CachedResponse { etag: etag.to_string(), body: String::from("{}"), link: None };
+
+    for i in 0..MAX_CACHED_RESPONSES {
+        cache.set_response(&token_key, &format!("/repos/{}", i), get_response(&i.to_string()));
+    }
+    assert!(cache.get_response(&token_key, "/repos/0").is_some());
+
+    cache.set_response(&token_key, "/user", get_response("user"));
+    assert!(cache.get_response(&token_key, "/repos/0").is_some());
+    assert!(cache.get_response(&token_key, "/repos/1").is_none());
+    assert!(cache.get_response(&token_key, "/repos/2").is_some());
+    assert_eq!(cache.get_response(&token_key, "/user").map(|response| response.etag), Some(String::from("user")));
+}
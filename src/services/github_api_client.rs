@@ -1,21 +1,150 @@
-use reqwest::header::{HeaderMap, HeaderValue,  AUTHORIZATION, USER_AGENT};
-use reqwest::{Client, Error, Response, StatusCode};
+use actix_rt::time::delay_for;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use reqwest::header::{HeaderMap, HeaderValue,  AUTHORIZATION, CACHE_CONTROL, ETAG, IF_NONE_MATCH, LINK, USER_AGENT};
+use reqwest::{Client, Error, RequestBuilder, Response, StatusCode};
 use serde::de::DeserializeOwned;
 use serde_json;
 
 use crate::errors::GITrelloError;
+use crate::models::github_profile::GithubProfile;
+use crate::services::github_api_cache::{get_rate_limit_reset, CachedResponse, GithubAPICache};
+use crate::state::State;
 use crate::value_objects::github_api::{
-    APIError, CreateWebhook, CreateWebhookConfig, GithubUser, Repository, Webhook,
+    APIError, CreateIssue, CreateWebhook, CreateWebhookConfig, GithubUser, Issue, Repository, UpdateIssue,
+    UpdateWebhook, Webhook, WebhookConfig,
 };
 
-const GITHUB_API_URL: &str = "https://api.github.com";
+pub const ISSUES_PER_PAGE: usize = 100;
+const REPOSITORIES_PER_PAGE: usize = 100;
+// Stops runaway pagination, 5000 repositories are more than anyone picks from
+const MAX_REPOSITORY_PAGES: usize = 50;
+// Short rate limit windows are waited out, longer ones fail fast
+const MAX_RATE_LIMIT_WAIT_SECONDS: i64 = 5;
+
+// Services get clients through State, so tests can replace GitHub with a fake
+#[async_trait(?Send)]
+pub trait GitHubAPI {
+    async fn get_user(&self) -> Result<GithubUser, GITrelloError>;
+
+    // The flag is set when pagination stopped before the last page
+    async fn get_repositories(
+        &self,
+        affiliation: &str,
+        visibility: &str,
+    ) -> Result<(Vec<Repository>, bool), GITrelloError>;
+
+    async fn get_repository(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+    ) -> Result<Repository, GITrelloError>;
+
+    async fn get_open_issues(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+        page: i32,
+    ) -> Result<Vec<Issue>, GITrelloError>;
+
+    async fn create_issue(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+        title: &str,
+        body: &str,
+    ) -> Result<Issue, GITrelloError>;
+
+    async fn update_issue_title(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+        issue_number: i32,
+        title: &str,
+    ) -> Result<Issue, GITrelloError>;
+
+    async fn close_issue(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+        issue_number: i32,
+    ) -> Result<Issue, GITrelloError>;
+
+    async fn reopen_issue(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+        issue_number: i32,
+    ) -> Result<Issue, GITrelloError>;
+
+    async fn create_webhook(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+        webhook_url: &str,
+        secret: &str,
+        events: Vec<String>,
+    ) -> Result<Webhook, GITrelloError>;
+
+    async fn get_webhooks(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+    ) -> Result<Vec<Webhook>, GITrelloError>;
+
+    async fn update_webhook_events(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+        webhook_id: i64,
+        events: Vec<String>,
+    ) -> Result<Webhook, GITrelloError>;
+
+    async fn update_webhook_config(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+        webhook_id: i64,
+        webhook_url: &str,
+        secret: &str,
+    ) -> Result<WebhookConfig, GITrelloError>;
+
+    async fn delete_webhook(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+        webhook_id: i64,
+    ) -> Result<bool, GITrelloError>;
+}
+
+pub trait GitHubAPIClientFactory: Send + Sync {
+    fn create(&self, state: &State, api_url: &str, access_token: &str) -> Box<dyn GitHubAPI>;
+}
+
+pub struct HttpGitHubAPIClientFactory;
+
+impl GitHubAPIClientFactory for HttpGitHubAPIClientFactory {
+    fn create(&self, state: &State, api_url: &str, access_token: &str) -> Box<dyn GitHubAPI> {
+        Box::new(GitHubAPIClient::new(state, api_url, access_token))
+    }
+}
+
+// Profiles on GitHub Enterprise Server talk to their own API
+pub fn get_github_api_client(state: &State, github_profile: &GithubProfile) -> Box<dyn GitHubAPI> {
+    let api_url = github_profile.github_api_url.as_deref().unwrap_or(state.github_api_url.as_str());
+    state.github_api_client_factory.create(state, api_url, github_profile.access_token.as_str())
+}
 
 pub struct GitHubAPIClient {
+    api_url: String,
+    client: Client,
     headers: HeaderMap,
+    cache: GithubAPICache,
+    token_key: String,
 }
 
 impl GitHubAPIClient {
-    pub fn new(access_token: &str) -> Self {
+    pub fn new(state: &State, api_url: &str, access_token: &str) -> Self {
         let authorization_header_value = HeaderValue::
         from_str(
             format!("Token {}", access_token).as_str())
@@ -30,7 +159,32 @@ impl GitHubAPIClient {
         headers.insert(AUTHORIZATION, authorization_header_value);
         headers.insert(USER_AGENT, user_agent_header_value);
 
-        Self { headers }
+        Self {
+            api_url: api_url.to_string(),
+            client: state.http_client.clone(),
+            headers,
+            cache: state.github_api_cache.clone(),
+            token_key: GithubAPICache::get_token_key(access_token),
+        }
+    }
+
+    async fn wait_for_rate_limit(&self) -> Result<(), GITrelloError> {
+        let now = Utc::now();
+        if let Some(blocked_until) = self.cache.get_blocked_until(&self.token_key, now) {
+            let wait = blocked_until - now;
+            if wait > Duration::seconds(MAX_RATE_LIMIT_WAIT_SECONDS) {
+                return Err(GITrelloError::RateLimitExceeded { retry_after: wait.num_seconds() + 1 });
+            }
+
+            delay_for(wait.to_std().unwrap_or_default()).await;
+        }
+
+        Ok(())
+    }
+
+    async fn send(&self, request: RequestBuilder) -> Result<Result<Response, Error>, GITrelloError> {
+        self.wait_for_rate_limit().await?;
+        Ok(request.headers(self.headers.clone()).send().await)
     }
 
     async fn process_response<T>(
@@ -43,6 +197,8 @@ impl GitHubAPIClient {
         match response {
             Ok(response) => {
                 if response.status() == expected_status_code {
+                    self.update_rate_limit(response.status(), response.headers(), "");
+
                     if expected_status_code == StatusCode::NO_CONTENT {
                         return Ok(None);
                     }
@@ -52,100 +208,303 @@ impl GitHubAPIClient {
                     // Response with valid code
                     json
                         .map(|json| Some(json))
-                        .map_err(|source| GITrelloError::GitHubAPIClientError {
-                        message: source.to_string(),
-                    })
+                        .map_err(GITrelloError::from_http_error)
                 }
                 else {
-                    let response_string = response.text().await;
-                    match response_string {
-                        Ok(response_string) => {
-                            let api_error: Result<APIError, _> = serde_json::from_str(
-                                response_string.as_str(),
-                            );
-                            match api_error {
-                                // Response with invalid code can be deserialized into APIError
-                                Ok(api_error) => {
-                                    Err(GITrelloError::GitHubAPIClientError {
-                                        message: api_error.message,
-                                    })
-                                },
-                                // Response with invalid code can not be deserialized into APIError
-                                _ => {
-                                    Err(GITrelloError::GitHubAPIClientError {
-                                        message: response_string,
-                                    })
-                                }
-                            }
-                        },
-                        // Response with invalid code can not be read into String
-                        Err(e) => {
-                            Err(GITrelloError::GitHubAPIClientError { message: e.to_string() })
-                        }
-                    }
+                    Err(self.process_error_response(response).await)
                 }
             }
             // HTTP Request failed
             Err(e) => {
-                Err(GITrelloError::HttpRequestError {source: e})
+                Err(GITrelloError::from_http_error(e))
             }
         }
     }
 
-    pub async fn get_user(&self) -> Result<GithubUser, GITrelloError> {
-        let url = format!("{}/{}", GITHUB_API_URL, "user");
+    async fn process_error_response(&self, response: Response) -> GITrelloError {
+        let status = response.status();
+        let headers = response.headers().clone();
+
+        let message = match response.text().await {
+            Ok(response_string) => {
+                match serde_json::from_str::<APIError>(response_string.as_str()) {
+                    // Response with invalid code can be deserialized into APIError
+                    Ok(api_error) => api_error.message,
+                    // Response with invalid code can not be deserialized into APIError
+                    _ => response_string,
+                }
+            },
+            // Response with invalid code can not be read into String
+            Err(e) => e.to_string(),
+        };
+
+        let is_rate_limited = self.update_rate_limit(status, &headers, message.as_str());
+        if is_rate_limited && (status == StatusCode::FORBIDDEN || status == StatusCode::TOO_MANY_REQUESTS) {
+            let now = Utc::now();
+            let blocked_until = self.cache.get_blocked_until(&self.token_key, now).unwrap_or(now);
+            return GITrelloError::RateLimitExceeded { retry_after: (blocked_until - now).num_seconds() + 1 };
+        }
+
+        GITrelloError::GitHubAPIClientError { message }
+    }
+
+    fn update_rate_limit(&self, status: StatusCode, headers: &HeaderMap, message: &str) -> bool {
+        match get_rate_limit_reset(status, headers, message, Utc::now()) {
+            Some(reset) => {
+                warn!("GitHub API rate limit is exhausted until {}", reset);
+                self.cache.block_until(&self.token_key, reset);
+                true
+            },
+            None => false,
+        }
+    }
+
+    // Conditional GET, an unchanged resource is answered with 304 which does not count against the rate limit
+    async fn get<T>(&self, url: &str) -> Result<(T, Option<String>), GITrelloError>
+        where T: DeserializeOwned
+    {
+        let cached_response = self.cache.get_response(&self.token_key, url);
+
+        let mut request = self.client.get(url);
+        if let Some(cached_response) = &cached_response {
+            request = request.header(IF_NONE_MATCH, cached_response.etag.as_str());
+        }
+
+        let mut response = self.send(request).await?.map_err(GITrelloError::from_http_error)?;
+
+        // 304 can not be answered without a cached body (e.g. it came from a proxy), so it is retried once
+        // without conditions
+        if response.status() == StatusCode::NOT_MODIFIED && cached_response.is_none() {
+            let request = self.client.get(url).header(CACHE_CONTROL, "no-cache");
+            response = self.send(request).await?.map_err(GITrelloError::from_http_error)?;
+        }
+
+        let status = response.status();
+
+        let cached_response = match (status, cached_response) {
+            (StatusCode::NOT_MODIFIED, Some(cached_response)) => cached_response,
+            (StatusCode::OK, _) => {
+                self.update_rate_limit(status, response.headers(), "");
+
+                let etag = response.headers().get(ETAG).and_then(|etag| etag.to_str().ok()).map(String::from);
+                let link = response.headers().get(LINK).and_then(|link| link.to_str().ok()).map(String::from);
+                let body = response
+                    .text()
+                    .await
+                    .map_err(GITrelloError::from_http_error)?;
+
+                let cached_response = CachedResponse { etag: etag.unwrap_or_default(), body, link };
+                if !cached_response.etag.is_empty() {
+                    self.cache.set_response(&self.token_key, url, cached_response.clone());
+                }
+                cached_response
+            },
+            _ => return Err(self.process_error_response(response).await),
+        };
+
+        let result = serde_json::from_str::<T>(cached_response.body.as_str())
+            .map_err(|source| GITrelloError::GitHubAPIClientError { message: source.to_string() })?;
+
+        Ok((result, cached_response.link))
+    }
+
+    async fn patch_issue(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+        issue_number: i32,
+        data: &UpdateIssue,
+    ) -> Result<Issue, GITrelloError>
+    {
+        let url = format!(
+            "{}/repos/{}/{}/issues/{}",
+            self.api_url,
+            repository_owner,
+            repository_name,
+            issue_number,
+        );
 
-        let response = Client::new()
-            .get(&url)
-            .headers(self.headers.clone())
-            .send()
-            .await;
+        let response = self
+            .send(self.client.patch(&url).json(data))
+            .await?;
 
-        let result = self.process_response::<GithubUser>(response, StatusCode::OK).await;
+        let result = self.process_response::<Issue>(response, StatusCode::OK).await;
         match result {
             Ok(result) => Ok(result.expect("can not be None")),
             Err(e) => Err(e),
         }
     }
+}
+
+#[async_trait(?Send)]
+impl GitHubAPI for GitHubAPIClient {
+    async fn get_user(&self) -> Result<GithubUser, GITrelloError> {
+        let url = format!("{}/{}", self.api_url, "user");
+
+        let (user, _) = self.get::<GithubUser>(&url).await?;
+        Ok(user)
+    }
+
+    // Follows the Link header until the last page
+    async fn get_repositories(
+        &self,
+        affiliation: &str,
+        visibility: &str,
+    ) -> Result<(Vec<Repository>, bool), GITrelloError>
+    {
+        let mut url = Some(format!(
+            "{}/user/repos?affiliation={}&visibility={}&per_page={}",
+            self.api_url,
+            affiliation,
+            visibility,
+            REPOSITORIES_PER_PAGE,
+        ));
+
+        let mut repositories = Vec::new();
+        let mut page = 0;
+        while let Some(page_url) = url {
+            if page == MAX_REPOSITORY_PAGES {
+                warn!("Stopped fetching repositories after {} pages", MAX_REPOSITORY_PAGES);
+                return Ok((repositories, true));
+            }
+
+            let (page_repositories, link) = self.get::<Vec<Repository>>(&page_url).await?;
+            url = link.as_deref().and_then(get_next_page_url);
+            repositories.extend(page_repositories);
+            page += 1;
+        }
+
+        Ok((repositories, false))
+    }
+
+    async fn get_repository(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+    ) -> Result<Repository, GITrelloError>
+    {
+        let url = format!("{}/repos/{}/{}", self.api_url, repository_owner, repository_name);
+
+        let (repository, _) = self.get::<Repository>(&url).await?;
+        Ok(repository)
+    }
 
-    pub async fn get_repositories(&self) -> Result<Vec<Repository>, GITrelloError> {
-        let url = format!("{}/user/repos?affiliation=owner", GITHUB_API_URL);
+    async fn get_open_issues(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+        page: i32,
+    ) -> Result<Vec<Issue>, GITrelloError>
+    {
+        let url = format!(
+            "{}/repos/{}/{}/issues?state=open&direction=asc&per_page={}&page={}",
+            self.api_url,
+            repository_owner,
+            repository_name,
+            ISSUES_PER_PAGE,
+            page,
+        );
 
-        let response = Client::new()
-            .get(&url)
-            .headers(self.headers.clone())
-            .send()
-            .await;
+        let (issues, _) = self.get::<Vec<Issue>>(&url).await?;
+        Ok(issues)
+    }
+
+    async fn create_issue(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+        title: &str,
+        body: &str,
+    ) -> Result<Issue, GITrelloError>
+    {
+        let url = format!("{}/repos/{}/{}/issues", self.api_url, repository_owner, repository_name);
+        let body = CreateIssue { title: title.to_string(), body: body.to_string() };
 
-        let result = self.process_response::<Vec<Repository>>(response, StatusCode::OK).await;
+        let response = self
+            .send(self.client.post(&url).json::<CreateIssue>(&body))
+            .await?;
+
+        let result = self.process_response::<Issue>(response, StatusCode::CREATED).await;
         match result {
             Ok(result) => Ok(result.expect("can not be None")),
             Err(e) => Err(e),
         }
     }
 
-    pub async fn create_webhook(
+    async fn update_issue_title(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+        issue_number: i32,
+        title: &str,
+    ) -> Result<Issue, GITrelloError>
+    {
+        self
+            .patch_issue(
+                repository_name,
+                repository_owner,
+                issue_number,
+                &UpdateIssue { title: Some(title.to_string()), ..Default::default() },
+            )
+            .await
+    }
+
+    async fn close_issue(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+        issue_number: i32,
+    ) -> Result<Issue, GITrelloError>
+    {
+        self
+            .patch_issue(
+                repository_name,
+                repository_owner,
+                issue_number,
+                &UpdateIssue { state: Some(String::from("closed")), ..Default::default() },
+            )
+            .await
+    }
+
+    async fn reopen_issue(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+        issue_number: i32,
+    ) -> Result<Issue, GITrelloError>
+    {
+        self
+            .patch_issue(
+                repository_name,
+                repository_owner,
+                issue_number,
+                &UpdateIssue { state: Some(String::from("open")), ..Default::default() },
+            )
+            .await
+    }
+
+    async fn create_webhook(
         &self,
         repository_name: &str,
         repository_owner: &str,
         webhook_url: &str,
+        secret: &str,
+        events: Vec<String>,
     ) -> Result<Webhook, GITrelloError>
     {
-        let url = format!("{}/repos/{}/{}/hooks", GITHUB_API_URL, repository_owner, repository_name);
+        let url = format!("{}/repos/{}/{}/hooks", self.api_url, repository_owner, repository_name);
         let body = CreateWebhook {
             config: CreateWebhookConfig {
                 url: webhook_url.to_string(),
                 content_type: String::from("json"),
+                secret: secret.to_string(),
             },
-            events: vec![String::from("issues"), ],
+            events,
         };
 
-        let response = Client::new()
-            .post(&url)
-            .json::<CreateWebhook>(&body)
-            .headers(self.headers.clone())
-            .send()
-            .await;
+        let response = self
+            .send(self.client.post(&url).json::<CreateWebhook>(&body))
+            .await?;
 
         let result = self.process_response::<Webhook>(response, StatusCode::CREATED).await;
         match result {
@@ -154,20 +513,85 @@ impl GitHubAPIClient {
         }
     }
 
-    pub async fn delete_webhook(
+    async fn get_webhooks(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+    ) -> Result<Vec<Webhook>, GITrelloError>
+    {
+        let url = format!("{}/repos/{}/{}/hooks", self.api_url, repository_owner, repository_name);
+
+        let (webhooks, _) = self.get::<Vec<Webhook>>(&url).await?;
+        Ok(webhooks)
+    }
+
+    async fn update_webhook_events(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+        webhook_id: i64,
+        events: Vec<String>,
+    ) -> Result<Webhook, GITrelloError>
+    {
+        let url = format!("{}/repos/{}/{}/hooks/{}", self.api_url, repository_owner, repository_name, webhook_id);
+        let body = UpdateWebhook { events };
+
+        let response = self
+            .send(self.client.patch(&url).json::<UpdateWebhook>(&body))
+            .await?;
+
+        let result = self.process_response::<Webhook>(response, StatusCode::OK).await;
+        match result {
+            Ok(result) => Ok(result.expect("can not be None")),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn update_webhook_config(
+        &self,
+        repository_name: &str,
+        repository_owner: &str,
+        webhook_id: i64,
+        webhook_url: &str,
+        secret: &str,
+    ) -> Result<WebhookConfig, GITrelloError>
+    {
+        let url = format!(
+            "{}/repos/{}/{}/hooks/{}/config",
+            self.api_url,
+            repository_owner,
+            repository_name,
+            webhook_id,
+        );
+        let body = CreateWebhookConfig {
+            url: webhook_url.to_string(),
+            content_type: String::from("json"),
+            secret: secret.to_string(),
+        };
+
+        let response = self
+            .send(self.client.patch(&url).json::<CreateWebhookConfig>(&body))
+            .await?;
+
+        let result = self.process_response::<WebhookConfig>(response, StatusCode::OK).await;
+        match result {
+            Ok(result) => Ok(result.expect("can not be None")),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn delete_webhook(
         &self,
         repository_name: &str,
         repository_owner: &str,
         webhook_id: i64,
     ) -> Result<bool, GITrelloError>
     {
-        let url = format!("{}/repos/{}/{}/hooks/{}", GITHUB_API_URL, repository_owner, repository_name, webhook_id);
+        let url = format!("{}/repos/{}/{}/hooks/{}", self.api_url, repository_owner, repository_name, webhook_id);
 
-        let response = Client::new()
-            .delete(&url)
-            .headers(self.headers.clone())
-            .send()
-            .await;
+        let response = self
+            .send(self.client.delete(&url))
+            .await?;
 
         let result = self.process_response::<bool>(response,StatusCode::NO_CONTENT).await;
         match result {
@@ -176,3 +600,36 @@ impl GitHubAPIClient {
         }
     }
 }
+
+// Link: <https://api.github.com/user/repos?page=2>; rel="next", <https://api.github.com/user/repos?page=5>; rel="last"
+pub fn get_next_page_url(link: &str) -> Option<String> {
+    link.split(',').find_map(|link_value| {
+        let mut parts = link_value.split(';');
+        let url = parts.next()?.trim();
+        let is_next = parts.any(|part| part.trim() == r#"rel="next""#);
+
+        match is_next && url.starts_with('<') && url.ends_with('>') {
+            true => Some(url[1..url.len() - 1].to_string()),
+            false => None,
+        }
+    })
+}
+
+#[test]
+fn test_get_next_page_url() {
+    assert_eq!(
+        get_next_page_url(
+            "<https://api.github.com/user/repos?per_page=100&page=2>; rel=\"next\", \
+            <https://api.github.com/user/repos?per_page=100&page=5>; rel=\"last\"",
+        ),
+        Some(String::from("https://api.github.com/user/repos?per_page=100&page=2")),
+    );
+    assert_eq!(
+        get_next_page_url(
+            "<https://api.github.com/user/repos?page=1>; rel=\"first\", \
+            <https://api.github.com/user/repos?page=4>; rel=\"prev\"",
+        ),
+        None,
+    );
+    assert_eq!(get_next_page_url(""), None);
+}
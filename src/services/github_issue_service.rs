@@ -0,0 +1,200 @@
+use std::slice;
+
+use actix::{Actor, Addr, Context, Handler};
+use actix_web::web::Data;
+
+use crate::errors::GITrelloError;
+use crate::models::board_repository::BoardRepository;
+use crate::models::issue_ticket::IssueTicket;
+#[cfg(test)]
+use crate::models::board_repository::EVENT_ISSUES;
+#[cfg(test)]
+use crate::services::fakes::{
+    get_fake_board_repository, get_fake_state, FakeGITrelloAPIClient, FakeGitHubAPIClient, FakeIssueTicketRepository,
+    GITrelloAPICall,
+};
+use crate::services::github_api_client::{get_github_api_client, GitHubAPI};
+use crate::services::github_profile_service::GithubProfileService;
+use crate::services::gitrello_api_client::get_gitrello_api_client;
+use crate::services::issue_ticket_service::IssueTicketService;
+use crate::services::repositories::board_repository::{
+    BoardRepositoryRepository, GetBoardRepositoryByIdMessage,
+};
+use crate::services::repositories::issue_ticket::{
+    CreateIssueTicketMessage, DeleteIssueTicketMessage, GetIssueTicketsByBoardRepositoryIdsAndIssueIdMessage,
+    GetIssueTicketsByBoardRepositoryIdsAndIssueNumberMessage, GetIssueTicketsByTicketIdMessage,
+    UpdateIssueTicketTicketIdMessage,
+};
+use crate::state::State;
+use crate::value_objects::github_api::Issue;
+
+pub struct GithubIssueService<'a> {
+    state: &'a Data<State>,
+    actor: Addr<BoardRepositoryRepository>,
+}
+
+impl<'a> GithubIssueService<'a> {
+    pub fn new(state: &'a Data<State>) -> Result<Self, GITrelloError> {
+        let connection = state.get_db_connection()?;
+        let actor = BoardRepositoryRepository::new(connection).start();
+        Ok(Self { state, actor })
+    }
+
+    // Permissions are not checked, callers have to authenticate GITrello or check can_mutate themselves
+    pub async fn get_board_repository(&self, id: i32) -> Result<BoardRepository, GITrelloError> {
+        self.actor
+            .send(GetBoardRepositoryByIdMessage { id })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })?
+    }
+
+    // The issue is created on behalf of the user who linked the repository to the board
+    pub async fn create_from_ticket(
+        &self,
+        board_repository: &BoardRepository,
+        ticket_id: i64,
+        title: &str,
+        body: &str,
+    ) -> Result<(Issue, IssueTicket), GITrelloError>
+    {
+        if board_repository.is_read_only {
+            return Err(GITrelloError::BadRequest {
+                message: format!("board_repository {} is read-only", board_repository.id),
+            });
+        }
+
+        let github_profile_service = GithubProfileService::new(self.state)?;
+        let github_profile = github_profile_service
+            .get_by_id(board_repository.github_profile_id)
+            .await?;
+
+        let github_api_client = get_github_api_client(self.state, &github_profile);
+        let issue_ticket_service = IssueTicketService::new(self.state)?;
+
+        create_issue_for_ticket(
+            self.state,
+            github_api_client.as_ref(),
+            &issue_ticket_service,
+            board_repository,
+            ticket_id,
+            title,
+            body,
+        )
+            .await
+    }
+}
+
+// The issues webhook may be processed before the mapping is stored, and then the board already has a ticket
+// created for the issue. The ticket the issue was created from replaces it, so the board keeps only one.
+async fn create_issue_for_ticket<A>(
+    state: &Data<State>,
+    github_api_client: &dyn GitHubAPI,
+    issue_ticket_service: &IssueTicketService<A>,
+    board_repository: &BoardRepository,
+    ticket_id: i64,
+    title: &str,
+    body: &str,
+) -> Result<(Issue, IssueTicket), GITrelloError>
+    where A: Actor<Context = Context<A>>
+        + Handler<CreateIssueTicketMessage>
+        + Handler<GetIssueTicketsByBoardRepositoryIdsAndIssueIdMessage>
+        + Handler<GetIssueTicketsByBoardRepositoryIdsAndIssueNumberMessage>
+        + Handler<GetIssueTicketsByTicketIdMessage>
+        + Handler<UpdateIssueTicketTicketIdMessage>
+        + Handler<DeleteIssueTicketMessage>
+{
+    let issue = github_api_client
+        .create_issue(
+            board_repository.repository_name.as_str(),
+            board_repository.repository_owner.as_str(),
+            title,
+            body,
+        )
+        .await?;
+
+    let result = issue_ticket_service
+        .create(board_repository.id, issue.id, issue.number, ticket_id)
+        .await;
+
+    let issue_ticket = match result {
+        Err(GITrelloError::AlreadyExists { message: _ }) => {
+            let existing_issue_ticket = issue_ticket_service
+                .get_by_board_repositories_and_issue_id(slice::from_ref(board_repository), issue.id)
+                .await?
+                .into_iter()
+                .next()
+                .ok_or_else(|| GITrelloError::NotFound {
+                    message: format!("issue_ticket for issue {} does not exist", issue.id),
+                })?;
+
+            if existing_issue_ticket.ticket_id == ticket_id {
+                existing_issue_ticket
+            } else {
+                let duplicate_ticket_id = existing_issue_ticket.ticket_id;
+                warn!(
+                    "Issue {} already has ticket {}, replacing it with ticket {}",
+                    issue.id,
+                    duplicate_ticket_id,
+                    ticket_id,
+                );
+
+                let issue_ticket = issue_ticket_service
+                    .update_ticket_id(existing_issue_ticket, ticket_id)
+                    .await?;
+
+                let gitrello_api_client = get_gitrello_api_client(state);
+                if let Err(e) = gitrello_api_client.archive_ticket(duplicate_ticket_id).await {
+                    error!("Failed to archive duplicate ticket {}: {}", duplicate_ticket_id, e);
+                }
+
+                issue_ticket
+            }
+        },
+        result => result?,
+    };
+
+    Ok((issue, issue_ticket))
+}
+
+#[actix_rt::test]
+async fn test_create_issue_for_ticket() {
+    let github = FakeGitHubAPIClient::default();
+    let gitrello = FakeGITrelloAPIClient::default();
+    let state = Data::new(get_fake_state(&github, &gitrello));
+    let board_repository = get_fake_board_repository(1, "backend", &[EVENT_ISSUES]);
+    let issue_ticket_service = IssueTicketService::with_actor(FakeIssueTicketRepository::default().start());
+
+    let result = create_issue_for_ticket(&state, &github, &issue_ticket_service, &board_repository, 10, "Title", "")
+        .await;
+
+    let (issue, issue_ticket) = result.unwrap();
+    assert_eq!((issue_ticket.issue_id, issue_ticket.ticket_id), (issue.id, 10));
+    assert_eq!(gitrello.get_calls(), vec![]);
+}
+
+#[actix_rt::test]
+async fn test_create_issue_for_ticket_after_issue_opened_webhook() {
+    let github = FakeGitHubAPIClient::default();
+    let gitrello = FakeGITrelloAPIClient::default();
+    let state = Data::new(get_fake_state(&github, &gitrello));
+    let board_repository = get_fake_board_repository(1, "backend", &[EVENT_ISSUES]);
+
+    // Fake GitHub gives the first issue number 1 and id 2, the webhook has already mapped it to ticket 20
+    let mut repository = FakeIssueTicketRepository::default();
+    repository.add_issue_ticket(board_repository.id, 2, 1, 20);
+    let issue_ticket_service = IssueTicketService::with_actor(repository.start());
+
+    let result = create_issue_for_ticket(&state, &github, &issue_ticket_service, &board_repository, 10, "Title", "")
+        .await;
+
+    let (issue, issue_ticket) = result.unwrap();
+    assert_eq!(issue.id, 2);
+    assert_eq!((issue_ticket.id, issue_ticket.ticket_id), (1, 10));
+    assert_eq!(gitrello.get_calls(), vec![GITrelloAPICall::ArchiveTicket { ticket_id: 20 }]);
+
+    let issue_tickets = issue_ticket_service
+        .get_by_board_repositories_and_issue_id(slice::from_ref(&board_repository), issue.id)
+        .await
+        .unwrap();
+    assert_eq!(issue_tickets.len(), 1);
+}
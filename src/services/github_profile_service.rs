@@ -5,10 +5,10 @@ use futures::future;
 use crate::errors::GITrelloError;
 use crate::models::github_profile::{GithubProfile, NewGithubProfile};
 use crate::models::github_webhook::{GithubWebhookWithRepositoryInfo};
-use crate::services::github_api_client::GitHubAPIClient;
+use crate::services::github_api_client::get_github_api_client;
 use crate::services::repositories::github_profile::{
-    GetGithubProfileByGithubUserIdMessage, GetGithubProfileByUserIdMessage, GithubProfileRepository,
-    CreateGithubProfileMessage, DeleteGithubProfileMessage,
+    GetGithubProfileByGithubUserIdMessage, GetGithubProfileByIdMessage, GetGithubProfileByUserIdMessage,
+    GithubProfileRepository, CreateGithubProfileMessage, DeleteGithubProfileMessage,
 };
 use crate::services::repositories::github_webhook::{
     GetDistinctWebhooksByGithubProfileIdMessage, GithubWebhookRepository,
@@ -27,6 +27,13 @@ impl<'a> GithubProfileService<'a> {
         Ok(Self { actor, state })
     }
 
+    pub async fn get_by_id(&self, id: i32) -> Result<GithubProfile, GITrelloError> {
+        self.actor
+            .send(GetGithubProfileByIdMessage { id })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })?
+    }
+
     pub async fn get_by_user_id(&self, user_id: i64) -> Result<GithubProfile, GITrelloError> {
         self.actor
             .send(GetGithubProfileByUserIdMessage { user_id })
@@ -34,9 +41,19 @@ impl<'a> GithubProfileService<'a> {
             .map_err(|source| GITrelloError::ActorError { source })?
     }
 
-    pub async fn create(&self, user_id: i64, access_token: &str) -> Result<GithubProfile, GITrelloError>
+    pub async fn create(
+        &self,
+        user_id: i64,
+        access_token: &str,
+        github_api_url: Option<String>,
+    ) -> Result<GithubProfile, GITrelloError>
     {
-        let github_api_client = GitHubAPIClient::new(access_token);
+        let github_api_url = validate_github_api_url(github_api_url, &self.state.github_enterprise_hosts)?;
+        let github_api_client = self.state.github_api_client_factory.create(
+            self.state,
+            github_api_url.as_deref().unwrap_or(self.state.github_api_url.as_str()),
+            access_token,
+        );
         let github_user = github_api_client.get_user().await?;
 
         let existing_github_profile = self.actor
@@ -62,6 +79,7 @@ impl<'a> GithubProfileService<'a> {
                                     github_user_id: github_user.id,
                                     github_login: github_user.login,
                                     access_token: access_token.to_string(),
+                                    github_api_url,
                                 },
                             })
                             .await
@@ -86,7 +104,7 @@ impl<'a> GithubProfileService<'a> {
             .await
             .map_err(|source| GITrelloError::ActorError { source })??;
 
-        let github_api_client = GitHubAPIClient::new(github_profile.access_token.as_str());
+        let github_api_client = get_github_api_client(self.state, &github_profile);
 
         let mut delete_webhook_futures = Vec::new();
         for webhook_info in webhooks.iter() {
@@ -107,3 +125,93 @@ impl<'a> GithubProfileService<'a> {
             .map_err(|source| GITrelloError::ActorError { source })?
     }
 }
+
+// Empty URL means the API configured for the whole service. Requests are sent from inside the cluster, so only
+// HTTPS URLs on hosts allowed by the operator are accepted, anything else could reach internal services.
+fn validate_github_api_url(
+    github_api_url: Option<String>,
+    allowed_hosts: &[String],
+) -> Result<Option<String>, GITrelloError>
+{
+    let github_api_url = match github_api_url {
+        Some(github_api_url) => github_api_url.trim().trim_end_matches('/').to_string(),
+        None => return Ok(None),
+    };
+
+    if github_api_url.is_empty() {
+        return Ok(None);
+    }
+
+    let host = match get_host(github_api_url.as_str()) {
+        Some(host) => host,
+        None => {
+            return Err(GITrelloError::BadRequest {
+                message: format!("{} is not a valid GitHub API URL, expected an https:// URL", github_api_url),
+            });
+        },
+    };
+
+    if !allowed_hosts.contains(&host) {
+        return Err(GITrelloError::BadRequest {
+            message: format!("{} is not an allowed GitHub Enterprise host", host),
+        });
+    }
+
+    Ok(Some(github_api_url))
+}
+
+// Host of an https:// URL without the port, URLs with credentials are not accepted
+fn get_host(url: &str) -> Option<String> {
+    let authority = url
+        .get(.."https://".len())
+        .filter(|scheme| scheme.eq_ignore_ascii_case("https://"))
+        .map(|scheme| &url[scheme.len()..])?
+        .split(['/', '?', '#'])
+        .next()?;
+    if authority.contains('@') {
+        return None;
+    }
+
+    let host = match authority.strip_prefix('[') {
+        Some(ipv6) => ipv6.split(']').next()?,
+        None => authority.split(':').next()?,
+    };
+    if host.is_empty() {
+        return None;
+    }
+
+    Some(host.to_lowercase())
+}
+
+#[test]
+fn test_validate_github_api_url() {
+    let allowed_hosts = vec![String::from("github.example.com")];
+
+    assert_eq!(validate_github_api_url(None, &allowed_hosts).unwrap(), None);
+    assert_eq!(validate_github_api_url(Some(String::from(" ")), &allowed_hosts).unwrap(), None);
+    assert_eq!(
+        validate_github_api_url(Some(String::from("https://github.example.com/api/v3/")), &allowed_hosts).unwrap(),
+        Some(String::from("https://github.example.com/api/v3")),
+    );
+    assert_eq!(
+        validate_github_api_url(Some(String::from("HTTPS://GitHub.example.com:8443/api/v3")), &allowed_hosts)
+            .unwrap(),
+        Some(String::from("HTTPS://GitHub.example.com:8443/api/v3")),
+    );
+
+    let invalid_urls = [
+        "github.example.com/api/v3",
+        "http://github.example.com/api/v3",
+        "https://github.example.com.evil.com/api/v3",
+        "https://user@169.254.169.254/latest",
+        "https://github.example.com@127.0.0.1/api/v3",
+        "https://localhost:8080",
+        "https://10.0.0.1/api/v3",
+        "https://[::1]/api/v3",
+        "https:///api/v3",
+    ];
+    for url in invalid_urls.iter() {
+        assert!(validate_github_api_url(Some(url.to_string()), &allowed_hosts).is_err(), "{}", url);
+    }
+    assert!(validate_github_api_url(Some(String::from("https://github.example.com")), &[]).is_err());
+}
@@ -0,0 +1,173 @@
+use actix_web::web::Data;
+
+use crate::entities::user::User;
+use crate::errors::GITrelloError;
+use crate::services::github_api_client::get_github_api_client;
+use crate::services::github_profile_service::GithubProfileService;
+use crate::state::State;
+use crate::value_objects::github_api::Repository;
+
+pub const AFFILIATIONS: [&str; 3] = ["owner", "collaborator", "organization_member"];
+pub const VISIBILITIES: [&str; 3] = ["all", "public", "private"];
+pub const DEFAULT_PER_PAGE: usize = 30;
+pub const MAX_PER_PAGE: usize = 100;
+
+pub struct GithubRepositoryService<'a> {
+    state: &'a Data<State>,
+    user: &'a User,
+}
+
+impl<'a> GithubRepositoryService<'a> {
+    pub fn new(state: &'a Data<State>, user: &'a User) -> Self {
+        Self { state, user }
+    }
+
+    // GitHub can not search by name within /user/repos, so all pages are fetched and filtered here.
+    // Returns the page, the total and whether the total is incomplete because GitHub returned too many repositories.
+    pub async fn get_page(
+        &self,
+        affiliations: &[String],
+        visibility: &str,
+        search: Option<&str>,
+        page: usize,
+        per_page: usize,
+    ) -> Result<(Vec<Repository>, usize, bool), GITrelloError>
+    {
+        validate_filters(affiliations, visibility, page, per_page)?;
+
+        let github_profile_service = GithubProfileService::new(self.state)?;
+        let github_profile = github_profile_service
+            .get_by_user_id(self.user.id.expect("already checked"))
+            .await?;
+
+        let github_api_client = get_github_api_client(self.state, &github_profile);
+        let (repositories, is_truncated) = github_api_client
+            .get_repositories(affiliations.join(",").as_str(), visibility)
+            .await?;
+
+        let repositories = filter_repositories(repositories, search);
+        let total = repositories.len();
+
+        Ok((paginate(repositories, page, per_page), total, is_truncated))
+    }
+}
+
+fn validate_filters(
+    affiliations: &[String],
+    visibility: &str,
+    page: usize,
+    per_page: usize,
+) -> Result<(), GITrelloError>
+{
+    if affiliations.is_empty() {
+        return Err(GITrelloError::BadRequest { message: String::from("Affiliation can not be empty") });
+    }
+
+    let invalid_affiliation = affiliations
+        .iter()
+        .find(|affiliation| !AFFILIATIONS.contains(&affiliation.as_str()));
+    if let Some(affiliation) = invalid_affiliation {
+        return Err(GITrelloError::BadRequest {
+            message: format!(
+                "{} is not a valid affiliation, expected one of {}",
+                affiliation,
+                AFFILIATIONS.join(", "),
+            ),
+        });
+    }
+
+    if !VISIBILITIES.contains(&visibility) {
+        return Err(GITrelloError::BadRequest {
+            message: format!(
+                "{} is not a valid visibility, expected one of {}",
+                visibility,
+                VISIBILITIES.join(", "),
+            ),
+        });
+    }
+
+    if page == 0 || per_page == 0 || per_page > MAX_PER_PAGE {
+        return Err(GITrelloError::BadRequest {
+            message: format!("page must be positive and per_page must be between 1 and {}", MAX_PER_PAGE),
+        });
+    }
+
+    Ok(())
+}
+
+// Case insensitive match against the full name, so both owners and names can be searched for
+pub fn filter_repositories(repositories: Vec<Repository>, search: Option<&str>) -> Vec<Repository> {
+    let search = match search.map(|search| search.trim().to_lowercase()) {
+        Some(search) if !search.is_empty() => search,
+        _ => return repositories,
+    };
+
+    repositories
+        .into_iter()
+        .filter(|repository| repository.full_name.to_lowercase().contains(search.as_str()))
+        .collect()
+}
+
+// Pages past the end, including ones too far to compute the offset of, are empty
+pub fn paginate<T>(items: Vec<T>, page: usize, per_page: usize) -> Vec<T> {
+    let offset = match page.checked_sub(1).and_then(|page| page.checked_mul(per_page)) {
+        Some(offset) => offset,
+        None => return vec![],
+    };
+
+    items
+        .into_iter()
+        .skip(offset)
+        .take(per_page)
+        .collect()
+}
+
+#[cfg(test)]
+fn get_test_repository(id: i64, full_name: &str) -> Repository {
+    Repository { id, full_name: String::from(full_name), default_branch: None }
+}
+
+#[test]
+fn test_filter_repositories() {
+    let repositories = vec![
+        get_test_repository(1, "octocat/Hello-World"),
+        get_test_repository(2, "octo-org/infra"),
+        get_test_repository(3, "hubot/hello-bot"),
+    ];
+
+    let ids = |repositories: Vec<Repository>| -> Vec<i64> {
+        repositories.iter().map(|repository| repository.id).collect()
+    };
+
+    let filtered = filter_repositories(repositories, Some("HELLO"));
+    assert_eq!(ids(filtered), vec![1, 3]);
+
+    let repositories = vec![get_test_repository(1, "octocat/hello-world"), get_test_repository(2, "octo-org/infra")];
+    assert_eq!(ids(filter_repositories(repositories, Some("octo-org/"))), vec![2]);
+
+    let repositories = vec![get_test_repository(1, "octocat/hello-world")];
+    assert_eq!(ids(filter_repositories(repositories, Some(" "))), vec![1]);
+}
+
+#[test]
+fn test_paginate() {
+    let items: Vec<i32> = (1..=7).collect();
+
+    assert_eq!(paginate(items.clone(), 1, 3), vec![1, 2, 3]);
+    assert_eq!(paginate(items.clone(), 3, 3), vec![7]);
+    assert!(paginate(items.clone(), 4, 3).is_empty());
+    assert!(paginate(items.clone(), 0, 3).is_empty());
+    assert!(paginate(items, usize::MAX, MAX_PER_PAGE).is_empty());
+}
+
+#[test]
+fn test_validate_filters() {
+    let affiliations = vec![String::from("owner"), String::from("organization_member")];
+
+    assert!(validate_filters(&affiliations, "all", 1, DEFAULT_PER_PAGE).is_ok());
+    assert!(validate_filters(&[], "all", 1, DEFAULT_PER_PAGE).is_err());
+    assert!(validate_filters(&[String::from("member")], "all", 1, DEFAULT_PER_PAGE).is_err());
+    assert!(validate_filters(&affiliations, "internal", 1, DEFAULT_PER_PAGE).is_err());
+    assert!(validate_filters(&affiliations, "all", 0, DEFAULT_PER_PAGE).is_err());
+    assert!(validate_filters(&affiliations, "all", 1, MAX_PER_PAGE + 1).is_err());
+}
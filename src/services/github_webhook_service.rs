@@ -1,31 +1,62 @@
-use actix::{Actor, Addr};
+use std::iter;
+use std::slice;
+
+use actix::{Actor, Addr, Context, Handler};
 use actix_web::web::{BytesMut, Data};
-use bytes::{Buf};
 use futures;
+use serde::de::DeserializeOwned;
 use serde_json;
 
 use crate::entities::user::User;
 use crate::errors::GITrelloError;
 use crate::models::github_webhook::{GithubWebhook, NewGithubWebhook};
-use crate::models::board_repository::BoardRepository;
-use crate::services::github_api_client::GitHubAPIClient;
+use crate::models::board_repository::{
+    BoardRepository, UpdateBoardRepository, EVENT_ISSUES, EVENT_ISSUE_COMMENT, EVENT_PULL_REQUEST, EVENT_PUSH,
+    EVENT_REPOSITORY,
+};
+use crate::models::issue_filter::IssueFilter;
+use crate::models::issue_ticket::IssueTicket;
+use crate::models::ticket_comment::{NewTicketComment, TicketComment};
+use crate::services::closing_references::parse_closing_references;
+#[cfg(test)]
+use crate::services::fakes::{
+    get_fake_board_repository, get_fake_state, FakeGITrelloAPIClient, FakeGitHubAPIClient, FakeGithubWebhookRepository,
+    GitHubAPICall,
+};
+use crate::services::github_api_client::{get_github_api_client, GitHubAPI};
 use crate::services::github_profile_service::GithubProfileService;
-use crate::services::gitrello_api_client::GITRelloAPIClient;
+use crate::services::gitrello_api_client::get_gitrello_api_client;
+use crate::services::issue_filter_service::{matches_filters, IssueFilterService};
+use crate::services::issue_ticket_service::IssueTicketService;
 use crate::services::repositories::board_repository::{
-    BoardRepositoryRepository, GetBoardRepositoryByRepositoryOwnerAndNameMessage,
+    BoardRepositoryRepository, DeleteBoardRepositoryMessage, GetBoardRepositoriesByRepositoryMessage,
+    UpdateBoardRepositoryMessage, UpdateRepositoryDataMessage,
 };
 use crate::services::repositories::github_webhook::{
-    CreateGithubWebhookMessage, GetByRepositoryNameAndOwnerMessage, GithubWebhookRepository,
-    UpdateWebhookIdMessage,
+    CreateGithubWebhookMessage, GetBoardRepositoriesByWebhookIdMessage, GetByBoardRepositoryIdMessage,
+    GetByRepositoryNameAndOwnerMessage, GetByWebhookIdMessage, GithubWebhookRepository, UpdateWebhookIdMessage,
 };
+use crate::services::repositories::ticket_comment::{
+    CreateTicketCommentMessage, DeleteTicketCommentMessage,
+    GetTicketCommentsByIssueTicketIdsAndCommentIdMessage, TicketCommentRepository,
+};
+use crate::services::ticket_outbox_service::{
+    get_idempotency_key, is_retryable, map_created_ticket, TicketOutboxService,
+};
+use crate::services::ticket_template::{render_ticket, TicketTemplateContext};
+use crate::services::webhook_signature::{generate_secret, verify_signature};
 use crate::state::State;
-use crate::value_objects::github_api::{Issue, Repository};
-use crate::value_objects::request_data::webhook::{IssueWebhookRequest};
+use crate::value_objects::github_api::{Comment, GithubUser, Issue, PullRequest, Repository};
+use crate::value_objects::request_data::webhook::{
+    InstallationWebhookRequest, IssueCommentWebhookRequest, IssueWebhookRequest, PingWebhookRequest,
+    PullRequestWebhookRequest, PushWebhookRequest, RepositoryChanges, RepositoryWebhookRequest,
+};
 
-pub struct GithubWebhookService<'a> {
+// Generic over the repository actor so tests can keep webhooks in memory
+pub struct GithubWebhookService<'a, A: Actor = GithubWebhookRepository> {
     state: &'a Data<State>,
-    actor: Addr<GithubWebhookRepository>,
-    github_api_client: GitHubAPIClient,
+    actor: Addr<A>,
+    github_api_client: Box<dyn GitHubAPI>,
 }
 
 impl<'a> GithubWebhookService<'a> {
@@ -42,10 +73,24 @@ impl<'a> GithubWebhookService<'a> {
             .get_by_user_id(user.id.expect("already checked"))
             .await?;
 
-        let github_api_client = GitHubAPIClient::new(github_profile.access_token.as_str());
+        let github_api_client = get_github_api_client(state, &github_profile);
 
         Ok(Self { actor, state, github_api_client })
     }
+}
+
+impl<'a, A> GithubWebhookService<'a, A>
+    where A: Actor<Context = Context<A>>
+        + Handler<CreateGithubWebhookMessage>
+        + Handler<UpdateWebhookIdMessage>
+        + Handler<GetByBoardRepositoryIdMessage>
+        + Handler<GetByRepositoryNameAndOwnerMessage>
+        + Handler<GetBoardRepositoriesByWebhookIdMessage>
+{
+    #[cfg(test)]
+    pub fn with_actor(state: &'a Data<State>, actor: Addr<A>, github_api_client: Box<dyn GitHubAPI>) -> Self {
+        Self { actor, state, github_api_client }
+    }
 
     pub async fn create_or_update(
         &self,
@@ -65,7 +110,11 @@ impl<'a> GithubWebhookService<'a> {
 
             match existing_github_webhook {
                 Some(existing_github_webhook) => {
-                    self.clone(existing_github_webhook, board_repository).await
+                    let github_webhook = self.clone(existing_github_webhook, board_repository).await?;
+                    self
+                        .update_events(github_webhook.webhook_id, repository_name, repository_owner)
+                        .await?;
+                    Ok(github_webhook)
                 },
                 None => {
                     self.create(board_repository, repository_name, repository_owner).await
@@ -85,7 +134,10 @@ impl<'a> GithubWebhookService<'a> {
             let github_webhook_to_update = github_webhooks_for_old_repository
                 .into_iter()
                 .find(|github_webhook| github_webhook.board_repository_id == board_repository.id)
-                .expect("can not be None");
+                .ok_or_else(|| GITrelloError::NotFound {
+                    message: format!("github_webhook for board_repository {} does not exist", board_repository.id),
+                })?;
+            let old_webhook_id = github_webhook_to_update.webhook_id;
 
             if count == 1 {
                 // Webhook is being used only by current BoardRepository, so we should delete it
@@ -99,28 +151,93 @@ impl<'a> GithubWebhookService<'a> {
                     .await?;
             }
 
-            match existing_github_webhook {
+            let github_webhook = match existing_github_webhook {
                 Some(existing_github_webhook) => {
-                    self
+                    let github_webhook = self
                         .update_from_existing_github_webhook(
                             github_webhook_to_update,
                             existing_github_webhook,
                         )
-                        .await
+                        .await?;
+                    self
+                        .update_events(github_webhook.webhook_id, repository_name, repository_owner)
+                        .await?;
+                    github_webhook
                 },
                 None => {
                     self
                         .update(
                             github_webhook_to_update,
+                            board_repository,
                             repository_name,
                             repository_owner,
                         )
-                        .await
+                        .await?
                 }
+            };
+
+            if count > 1 {
+                // Remaining boards keep using the old webhook, it may not need all events anymore
+                self
+                    .update_events(
+                        old_webhook_id,
+                        board_repository.repository_name.as_str(),
+                        board_repository.repository_owner.as_str(),
+                    )
+                    .await?;
             }
+
+            Ok(github_webhook)
         }
     }
 
+    // Subscribes the webhook to the union of events of all board repositories sharing it
+    pub async fn update_events(
+        &self,
+        webhook_id: i64,
+        repository_name: &str,
+        repository_owner: &str,
+    ) -> Result<(), GITrelloError>
+    {
+        let board_repositories = self.actor
+            .send(GetBoardRepositoriesByWebhookIdMessage { webhook_id })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })??;
+
+        if board_repositories.is_empty() {
+            return Ok(());
+        }
+
+        self.github_api_client
+            .update_webhook_events(
+                repository_name,
+                repository_owner,
+                webhook_id,
+                get_webhook_events(&board_repositories),
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub async fn update_events_by_board_repository(
+        &self,
+        board_repository: &BoardRepository,
+    ) -> Result<(), GITrelloError>
+    {
+        let github_webhook = self.actor
+            .send(GetByBoardRepositoryIdMessage { board_repository_id: board_repository.id })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })??;
+
+        self
+            .update_events(
+                github_webhook.webhook_id,
+                board_repository.repository_name.as_str(),
+                board_repository.repository_owner.as_str(),
+            )
+            .await
+    }
+
     async fn create(
         &self,
         board_repository: &BoardRepository,
@@ -128,11 +245,14 @@ impl<'a> GithubWebhookService<'a> {
         repository_owner: &str,
     ) -> Result<GithubWebhook, GITrelloError>
     {
+        let secret = generate_secret();
         let webhook = self.github_api_client
             .create_webhook(
                 repository_name,
                 repository_owner,
                 self.state.webhook_url.as_str(),
+                secret.as_str(),
+                get_webhook_events(slice::from_ref(board_repository)),
             )
             .await?;
 
@@ -140,6 +260,7 @@ impl<'a> GithubWebhookService<'a> {
             webhook_id: webhook.id,
             board_repository_id: board_repository.id,
             url: self.state.webhook_url.clone(),
+            secret,
         };
 
         self.actor
@@ -151,20 +272,31 @@ impl<'a> GithubWebhookService<'a> {
     async fn update(
         &self,
         github_webhook: GithubWebhook,
+        board_repository: &BoardRepository,
         repository_name: &str,
         repository_owner: &str,
     ) -> Result<GithubWebhook, GITrelloError>
     {
+        let secret = generate_secret();
         let new_webhook = self.github_api_client
             .create_webhook(
                 repository_name,
                 repository_owner,
                 self.state.webhook_url.as_str(),
+                secret.as_str(),
+                get_webhook_events(slice::from_ref(board_repository)),
             )
             .await?;
 
         self.actor
-            .send(UpdateWebhookIdMessage { github_webhook, webhook_id: new_webhook.id })
+            .send(
+                UpdateWebhookIdMessage {
+                    github_webhook,
+                    webhook_id: new_webhook.id,
+                    url: self.state.webhook_url.clone(),
+                    secret,
+                },
+            )
             .await
             .map_err(|source| GITrelloError::ActorError { source })?
     }
@@ -179,6 +311,7 @@ impl<'a> GithubWebhookService<'a> {
             webhook_id: github_webhook.webhook_id,
             board_repository_id: board_repository.id,
             url: self.state.webhook_url.clone(),
+            secret: github_webhook.secret.clone(),
         };
 
         self.actor
@@ -197,6 +330,8 @@ impl<'a> GithubWebhookService<'a> {
                 UpdateWebhookIdMessage {
                     github_webhook: github_webhook_to_update,
                     webhook_id: existing_github_webhook.webhook_id,
+                    url: existing_github_webhook.url.clone(),
+                    secret: existing_github_webhook.secret.clone(),
                 },
             )
             .await
@@ -230,59 +365,1095 @@ impl<'a> GithubWebhookProcessingService<'a> {
         Self { state }
     }
 
+    pub async fn verify_signature(
+        &self,
+        webhook_id: i64,
+        signature: Option<&str>,
+        request_body: &[u8],
+    ) -> Result<(), GITrelloError>
+    {
+        let connection = self.state.get_db_connection()?;
+        let actor = GithubWebhookRepository::new(connection).start();
+
+        let github_webhook = actor
+            .send(GetByWebhookIdMessage { webhook_id })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })?
+            .map_err(|e| {
+                match e {
+                    GITrelloError::NotFound { message: _ } => GITrelloError::InvalidSignature,
+                    _ => e
+                }
+            })?;
+
+        // Hooks registered before deliveries were signed can not be verified until reconciliation sets a secret
+        if github_webhook.secret.is_empty() {
+            warn!("Webhook {} has no secret yet, rejecting the delivery", webhook_id);
+            return Err(GITrelloError::InvalidSignature);
+        }
+
+        match signature.map(|signature| verify_signature(github_webhook.secret.as_str(), request_body, signature)) {
+            Some(true) => Ok(()),
+            _ => Err(GITrelloError::InvalidSignature),
+        }
+    }
+
     pub async fn process(&self, event_type: &str, request_body: BytesMut) -> Result<(), GITrelloError>{
         match event_type {
-            "issues" => {
-                let request_json = serde_json::from_slice::<IssueWebhookRequest>(
-                        request_body.bytes(),
-                    )
-                    .map_err(|_| GITrelloError::InternalError)?;
+            "ping" => {
+                let request_json = parse_webhook_request::<PingWebhookRequest>(event_type, &request_body)?;
+
+                let repository = request_json.repository
+                    .as_ref()
+                    .map(|repository| repository.full_name.as_str())
+                    .unwrap_or("an organization");
+                let events = request_json.hook
+                    .map(|hook| hook.events.join(", "))
+                    .unwrap_or_default();
+                info!(
+                    "Webhook {} of {} subscribed to [{}] has been pinged: {}",
+                    request_json.hook_id,
+                    repository,
+                    events,
+                    request_json.zen,
+                );
+            },
+            "installation" => {
+                let request_json = parse_webhook_request::<InstallationWebhookRequest>(event_type, &request_body)?;
+
+                // Webhooks are managed with tokens of GitHub profiles, app installations are only logged
+                let repositories: Vec<&str> = request_json.repositories
+                    .iter()
+                    .map(|repository| repository.full_name.as_str())
+                    .collect();
+                info!(
+                    "Installation {} for {} with repositories [{}] has been {} by {}",
+                    request_json.installation.id,
+                    request_json.installation.account.login,
+                    repositories.join(", "),
+                    request_json.action,
+                    request_json.sender.login,
+                );
+            },
+            EVENT_ISSUES => {
+                let request_json = parse_webhook_request::<IssueWebhookRequest>(event_type, &request_body)?;
 
                 match request_json.action.as_str() {
                     "opened" => {
-                        self.process_issue_opened(&request_json.issue, &request_json.repository).await?;
+                        self
+                            .process_issue_opened(
+                                &request_json.issue,
+                                &request_json.repository,
+                                &request_json.sender,
+                            )
+                            .await?;
+                    },
+                    "edited" | "closed" | "reopened" | "deleted" | "transferred" => {
+                        self
+                            .process_issue_changed(
+                                request_json.action.as_str(),
+                                &request_json.issue,
+                                &request_json.repository,
+                                &request_json.sender,
+                            )
+                            .await?;
                     },
                     _ => ()
                 }
             },
+            EVENT_PULL_REQUEST => {
+                let request_json = parse_webhook_request::<PullRequestWebhookRequest>(event_type, &request_body)?;
+
+                let mut errors = vec![];
+                if let Err(e) = self
+                    .process_pull_request(
+                        request_json.action.as_str(),
+                        &request_json.pull_request,
+                        &request_json.repository,
+                        &request_json.sender,
+                    )
+                    .await
+                {
+                    errors.push(e);
+                }
+
+                let pull_request = &request_json.pull_request;
+                if request_json.action == "closed" && pull_request.merged {
+                    let issue_numbers = parse_closing_references(
+                        pull_request.body.as_deref().unwrap_or_default(),
+                    );
+                    if let Err(e) = self
+                        .close_referenced_tickets(
+                            &request_json.repository,
+                            EVENT_PULL_REQUEST,
+                            &issue_numbers,
+                            format!("pull request #{}", pull_request.number).as_str(),
+                            &[&request_json.sender, &pull_request.user],
+                        )
+                        .await
+                    {
+                        errors.push(e);
+                    }
+                }
+                GITrelloError::aggregate(errors)?;
+            },
+            EVENT_PUSH => {
+                let request_json = parse_webhook_request::<PushWebhookRequest>(event_type, &request_body)?;
+
+                self.process_push(&request_json).await?;
+            },
+            EVENT_REPOSITORY => {
+                let request_json = parse_webhook_request::<RepositoryWebhookRequest>(event_type, &request_body)?;
+
+                self.process_repository(&request_json).await?;
+            },
+            EVENT_ISSUE_COMMENT => {
+                let request_json = parse_webhook_request::<IssueCommentWebhookRequest>(event_type, &request_body)?;
+
+                self
+                    .process_issue_comment(
+                        request_json.action.as_str(),
+                        &request_json.issue,
+                        &request_json.comment,
+                        &request_json.repository,
+                        &request_json.sender,
+                    )
+                    .await?;
+            },
             _ => ()
         }
 
         Ok(())
     }
 
-    pub async fn process_issue_opened(&self, issue: &Issue, repository: &Repository) -> Result<(), GITrelloError>{
-        let parts = repository.split_full_name();
+    pub async fn process_issue_opened(
+        &self,
+        issue: &Issue,
+        repository: &Repository,
+        sender: &GithubUser,
+    ) -> Result<(), GITrelloError>
+    {
+        let mut board_repositories = self
+            .get_board_repositories(repository, EVENT_ISSUES, &[sender, &issue.user])
+            .await?;
+
+        let issue_filter_service = IssueFilterService::new(self.state)?;
+        let issue_filters = issue_filter_service.get_by_board_repositories(&board_repositories).await?;
+        board_repositories.retain(|board_repository| {
+            let board_repository_filters: Vec<IssueFilter> = issue_filters
+                .iter()
+                .filter(|issue_filter| issue_filter.board_repository_id == board_repository.id)
+                .cloned()
+                .collect();
+
+            matches_filters(&board_repository_filters, issue)
+        });
+
+        if board_repositories.is_empty() {
+            return Ok(());
+        }
+
+        let issue_ticket_service = IssueTicketService::new(self.state)?;
+        let issue_tickets = issue_ticket_service
+            .get_by_board_repositories_and_issue_id(&board_repositories, issue.id)
+            .await?;
+
+        self
+            .create_tickets(
+                board_repositories,
+                &issue_tickets,
+                issue.id,
+                issue.number,
+                &TicketTemplateContext::from_issue(repository.full_name.as_str(), issue),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn process_issue_changed(
+        &self,
+        action: &str,
+        issue: &Issue,
+        repository: &Repository,
+        sender: &GithubUser,
+    ) -> Result<(), GITrelloError>
+    {
+        let board_repositories = self
+            .get_board_repositories(repository, EVENT_ISSUES, &[sender, &issue.user])
+            .await?;
+
+        let issue_ticket_service = IssueTicketService::new(self.state)?;
+        let issue_tickets = issue_ticket_service
+            .get_by_board_repositories_and_issue_id(&board_repositories, issue.id)
+            .await?;
+
+        let gitrello_api_client = get_gitrello_api_client(self.state);
+
+        // Edited tickets are rendered with the templates of their boards, the same way they were created
+        let context = TicketTemplateContext::from_issue(repository.full_name.as_str(), issue);
+        let tickets: Vec<Option<(String, String)>> = issue_tickets
+            .iter()
+            .map(|issue_ticket| {
+                board_repositories
+                    .iter()
+                    .find(|board_repository| board_repository.id == issue_ticket.board_repository_id)
+                    .map(|board_repository| render_ticket(board_repository, &context))
+            })
+            .collect();
+
+        let update_ticket_futures = issue_tickets
+            .iter()
+            .zip(tickets.iter())
+            .map(|(issue_ticket, ticket)| {
+                let gitrello_api_client = &gitrello_api_client;
+                async move {
+                    match (action, ticket) {
+                        ("edited", Some((title, body))) => {
+                            gitrello_api_client.update_ticket(issue_ticket.ticket_id, title, body).await
+                        },
+                        ("edited", None) => Err(GITrelloError::NotFound {
+                            message: format!("board_repository {} does not exist", issue_ticket.board_repository_id),
+                        }),
+                        ("closed", _) => gitrello_api_client.close_ticket(issue_ticket.ticket_id).await,
+                        ("reopened", _) => gitrello_api_client.reopen_ticket(issue_ticket.ticket_id).await,
+                        _ => gitrello_api_client.archive_ticket(issue_ticket.ticket_id).await,
+                    }
+                }
+            });
+
+        let results = futures::future::join_all(update_ticket_futures).await;
+
+        let mut errors = Vec::new();
+        for (issue_ticket, result) in issue_tickets.iter().zip(results) {
+            if let Err(e) = result {
+                error!("Failed to {} ticket {}: {}", action, issue_ticket.ticket_id, e);
+                errors.push(e);
+                continue;
+            }
+
+            // Issue no longer exists in this repository, so it can not be synced anymore
+            if action == "deleted" || action == "transferred" {
+                if let Err(e) = issue_ticket_service.delete(issue_ticket.id).await {
+                    error!("Failed to delete issue ticket {}: {}", issue_ticket.id, e);
+                    errors.push(e);
+                }
+            }
+        }
+
+        GITrelloError::aggregate(errors)
+    }
+
+    pub async fn process_issue_comment(
+        &self,
+        action: &str,
+        issue: &Issue,
+        comment: &Comment,
+        repository: &Repository,
+        sender: &GithubUser,
+    ) -> Result<(), GITrelloError>
+    {
+        let board_repositories = self
+            .get_board_repositories(repository, EVENT_ISSUE_COMMENT, &[sender, &issue.user, &comment.user])
+            .await?;
+
+        let issue_ticket_service = IssueTicketService::new(self.state)?;
+        let issue_tickets = issue_ticket_service
+            .get_by_board_repositories_and_issue_id(&board_repositories, issue.id)
+            .await?;
+
+        let connection = self.state.get_db_connection()?;
+        let actor = TicketCommentRepository::new(connection).start();
+
+        let ticket_comments: Vec<TicketComment> = actor
+            .send(GetTicketCommentsByIssueTicketIdsAndCommentIdMessage {
+                issue_ticket_ids: issue_tickets.iter().map(|issue_ticket| issue_ticket.id).collect(),
+                comment_id: comment.id,
+            })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })??;
+
+        let gitrello_api_client = get_gitrello_api_client(self.state);
+        let body = format_ticket_comment(comment);
+        let mut errors = vec![];
+
+        match action {
+            "created" => {
+                for issue_ticket in issue_tickets.iter() {
+                    // Comment may already be mirrored if the same event has been processed before
+                    let is_mirrored = ticket_comments
+                        .iter()
+                        .any(|ticket_comment| ticket_comment.issue_ticket_id == issue_ticket.id);
+                    if is_mirrored {
+                        continue;
+                    }
+
+                    let gitrello_comment = match gitrello_api_client
+                        .create_ticket_comment(issue_ticket.ticket_id, body.as_str())
+                        .await
+                    {
+                        Ok(gitrello_comment) => gitrello_comment,
+                        Err(e) => {
+                            errors.push(e);
+                            continue;
+                        },
+                    };
+
+                    let result = actor
+                        .send(CreateTicketCommentMessage {
+                            data: NewTicketComment {
+                                issue_ticket_id: issue_ticket.id,
+                                comment_id: comment.id,
+                                ticket_comment_id: gitrello_comment.id,
+                            },
+                        })
+                        .await
+                        .map_err(|source| GITrelloError::ActorError { source })
+                        .and_then(|result| result);
+                    if let Err(e) = result {
+                        errors.push(e);
+                    }
+                }
+            },
+            "edited" | "deleted" => {
+                for ticket_comment in ticket_comments.iter() {
+                    let issue_ticket = issue_tickets
+                        .iter()
+                        .find(|issue_ticket| issue_ticket.id == ticket_comment.issue_ticket_id)
+                        .expect("ticket_comments are filtered by issue_tickets");
+
+                    if action == "edited" {
+                        if let Err(e) = gitrello_api_client
+                            .update_ticket_comment(
+                                issue_ticket.ticket_id,
+                                ticket_comment.ticket_comment_id,
+                                body.as_str(),
+                            )
+                            .await
+                        {
+                            errors.push(e);
+                        }
+                    } else {
+                        if let Err(e) = gitrello_api_client
+                            .delete_ticket_comment(issue_ticket.ticket_id, ticket_comment.ticket_comment_id)
+                            .await
+                        {
+                            errors.push(e);
+                            continue;
+                        }
+
+                        let result = actor
+                            .send(DeleteTicketCommentMessage { id: ticket_comment.id })
+                            .await
+                            .map_err(|source| GITrelloError::ActorError { source })
+                            .and_then(|result| result);
+                        if let Err(e) = result {
+                            errors.push(e);
+                        }
+                    }
+                }
+            },
+            _ => ()
+        }
+
+        GITrelloError::aggregate(errors)
+    }
+
+    pub async fn process_pull_request(
+        &self,
+        action: &str,
+        pull_request: &PullRequest,
+        repository: &Repository,
+        sender: &GithubUser,
+    ) -> Result<(), GITrelloError>
+    {
+        let board_repositories: Vec<BoardRepository> = self
+            .get_board_repositories(repository, EVENT_PULL_REQUEST, &[sender, &pull_request.user])
+            .await?
+            .into_iter()
+            .filter(|board_repository| board_repository.sync_pull_requests)
+            .collect();
+
+        if board_repositories.is_empty() {
+            return Ok(());
+        }
+
+        let issue_ticket_service = IssueTicketService::new(self.state)?;
+        let issue_tickets = issue_ticket_service
+            .get_by_board_repositories_and_issue_number(&board_repositories, pull_request.number)
+            .await?;
+
+        let gitrello_api_client = get_gitrello_api_client(self.state);
+
+        match action {
+            "opened" | "ready_for_review" => {
+                // Draft pull requests get their tickets once they are ready for review
+                if pull_request.draft {
+                    return Ok(());
+                }
+
+                self
+                    .create_tickets(
+                        board_repositories,
+                        &issue_tickets,
+                        pull_request.id,
+                        pull_request.number,
+                        &TicketTemplateContext::from_pull_request(repository.full_name.as_str(), pull_request),
+                    )
+                    .await?;
+            },
+            "closed" => {
+                let comment = format_pull_request_state(pull_request);
+                let mut errors = vec![];
+                for issue_ticket in issue_tickets.iter() {
+                    if let Err(e) = gitrello_api_client.close_ticket(issue_ticket.ticket_id).await {
+                        errors.push(e);
+                        continue;
+                    }
+                    if let Err(e) = gitrello_api_client
+                        .create_ticket_comment(issue_ticket.ticket_id, comment.as_str())
+                        .await
+                    {
+                        errors.push(e);
+                    }
+                }
+                return GITrelloError::aggregate(errors);
+            },
+            "reopened" => {
+                let mut errors = vec![];
+                for issue_ticket in issue_tickets.iter() {
+                    if let Err(e) = gitrello_api_client.reopen_ticket(issue_ticket.ticket_id).await {
+                        errors.push(e);
+                    }
+                }
+                return GITrelloError::aggregate(errors);
+            },
+            _ => ()
+        }
+
+        Ok(())
+    }
+
+    pub async fn process_push(&self, request_json: &PushWebhookRequest) -> Result<(), GITrelloError> {
+        // Like GitHub, only commits that land on the default branch close issues
+        let default_branch = match &request_json.repository.default_branch {
+            Some(default_branch) => default_branch,
+            None => return Ok(()),
+        };
+        if request_json.git_ref != format!("refs/heads/{}", default_branch) {
+            return Ok(());
+        }
+
+        let mut errors = vec![];
+        for commit in request_json.commits.iter() {
+            let issue_numbers = parse_closing_references(commit.message.as_str());
+            if let Err(e) = self
+                .close_referenced_tickets(
+                    &request_json.repository,
+                    EVENT_PUSH,
+                    &issue_numbers,
+                    format!("commit {}", commit.id).as_str(),
+                    &[&request_json.sender],
+                )
+                .await
+            {
+                errors.push(e);
+            }
+        }
+
+        GITrelloError::aggregate(errors)
+    }
+
+    pub async fn process_repository(&self, request_json: &RepositoryWebhookRequest) -> Result<(), GITrelloError> {
+        let action = request_json.action.as_str();
+        match action {
+            "renamed" | "transferred" | "archived" | "unarchived" | "deleted" => (),
+            _ => return Ok(()),
+        }
+
+        let repository = &request_json.repository;
+        let (repository_owner, repository_name) = repository.split_full_name();
+
+        // Links without a stored repository id still have the name the repository had before the change
+        let (previous_owner, previous_name) = get_previous_owner_and_name(repository, &request_json.changes)
+            .unwrap_or((repository_owner.to_string(), repository_name.to_string()));
 
         let connection = self.state.get_db_connection()?;
         let actor = BoardRepositoryRepository::new(connection).start();
 
         let board_repositories: Vec<BoardRepository> = actor
-            .send(GetBoardRepositoryByRepositoryOwnerAndNameMessage {
-                repository_owner: parts.0.to_string(),
-                repository_name: parts.1.to_string(),
+            .send(GetBoardRepositoriesByRepositoryMessage {
+                repository_id: repository.id,
+                repository_owner: previous_owner,
+                repository_name: previous_name,
             })
             .await
             .map_err(|source| GITrelloError::ActorError { source })??;
 
-        let gitrello_api_client = GITRelloAPIClient::with_access_token(
-            &self.state.gitrello_url,
-            &self.state.gitrello_access_token,
-        );
+        for board_repository in board_repositories {
+            let board_repository_id = board_repository.id;
+
+            match action {
+                "renamed" | "transferred" => {
+                    actor
+                        .send(UpdateRepositoryDataMessage {
+                            board_repository,
+                            repository_name: repository_name.to_string(),
+                            repository_owner: repository_owner.to_string(),
+                            repository_id: Some(repository.id),
+                        })
+                        .await
+                        .map_err(|source| GITrelloError::ActorError { source })??;
+                },
+                "archived" | "unarchived" => {
+                    actor
+                        .send(UpdateBoardRepositoryMessage {
+                            board_repository,
+                            data: UpdateBoardRepository {
+                                is_read_only: Some(action == "archived"),
+                                ..Default::default()
+                            },
+                        })
+                        .await
+                        .map_err(|source| GITrelloError::ActorError { source })??;
+                },
+                // github_webhook rows are removed along with the link, the hook itself is gone with the repository
+                _ => {
+                    actor
+                        .send(DeleteBoardRepositoryMessage { id: board_repository_id })
+                        .await
+                        .map_err(|source| GITrelloError::ActorError { source })??;
+                },
+            }
+
+            info!(
+                "board_repository {} has been updated after repository {} was {} by {}",
+                board_repository_id,
+                repository.full_name,
+                action,
+                request_json.sender.login,
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn close_referenced_tickets(
+        &self,
+        repository: &Repository,
+        event: &str,
+        issue_numbers: &[i32],
+        closed_by: &str,
+        actors: &[&GithubUser],
+    ) -> Result<(), GITrelloError>
+    {
+        if issue_numbers.is_empty() {
+            return Ok(());
+        }
+
+        let board_repositories = self.get_board_repositories(repository, event, actors).await?;
+        let issue_ticket_service = IssueTicketService::new(self.state)?;
+        let gitrello_api_client = get_gitrello_api_client(self.state);
+
+        let mut errors = vec![];
+        for issue_number in issue_numbers.iter() {
+            let issue_tickets = match issue_ticket_service
+                .get_by_board_repositories_and_issue_number(&board_repositories, *issue_number)
+                .await
+            {
+                Ok(issue_tickets) => issue_tickets,
+                Err(e) => {
+                    errors.push(e);
+                    continue;
+                },
+            };
+
+            for issue_ticket in issue_tickets.iter() {
+                if let Err(e) = gitrello_api_client.close_ticket(issue_ticket.ticket_id).await {
+                    errors.push(e);
+                    continue;
+                }
+                info!(
+                    "Ticket {} for {}#{} has been closed by {}",
+                    issue_ticket.ticket_id,
+                    repository.full_name,
+                    issue_number,
+                    closed_by,
+                );
+            }
+        }
+
+        GITrelloError::aggregate(errors)
+    }
+
+    // Creates tickets on boards that do not have one for the given issue yet, retryable failures go to the outbox.
+    // Returns mappings of the tickets created right away.
+    pub async fn create_tickets(
+        &self,
+        board_repositories: Vec<BoardRepository>,
+        issue_tickets: &[IssueTicket],
+        issue_id: i64,
+        issue_number: i32,
+        context: &TicketTemplateContext,
+    ) -> Result<Vec<IssueTicket>, GITrelloError>
+    {
+        let board_repositories: Vec<BoardRepository> = board_repositories
+            .into_iter()
+            .filter(|board_repository| {
+                !issue_tickets
+                    .iter()
+                    .any(|issue_ticket| issue_ticket.board_repository_id == board_repository.id)
+            })
+            .collect();
+
+        let gitrello_api_client = get_gitrello_api_client(self.state);
+
+        // Every board has its own templates
+        let tickets: Vec<(String, String)> = board_repositories
+            .iter()
+            .map(|board_repository| render_ticket(board_repository, context))
+            .collect();
+
+        let idempotency_keys: Vec<String> = board_repositories
+            .iter()
+            .map(|board_repository| get_idempotency_key(board_repository.id, issue_id))
+            .collect();
 
         let mut create_ticket_futures = Vec::new();
-        for board_repository in board_repositories.iter() {
+        let board_repositories_with_keys = board_repositories.iter().zip(idempotency_keys.iter());
+        for ((board_repository, idempotency_key), (title, body)) in board_repositories_with_keys.zip(tickets.iter()) {
             create_ticket_futures.push(
-                gitrello_api_client.create_ticket(
-                    board_repository.board_id,
-                    issue.title.as_str(),
-                    issue.body.as_str(),
-                ),
+                gitrello_api_client.create_ticket(board_repository.board_id, title, body, Some(idempotency_key)),
             );
         }
 
-        futures::future::join_all(create_ticket_futures).await;
+        let results = futures::future::join_all(create_ticket_futures).await;
 
-        Ok(())
+        let issue_ticket_service = IssueTicketService::new(self.state)?;
+        let ticket_outbox_service = TicketOutboxService::new(self.state)?;
+        let board_repositories_with_tickets = board_repositories.iter().zip(tickets.iter());
+        let mut created_issue_tickets = Vec::new();
+        let mut errors = Vec::new();
+        for ((board_repository, (title, body)), result) in board_repositories_with_tickets.zip(results) {
+            match result {
+                Ok(ticket) => {
+                    let result = map_created_ticket(
+                        &issue_ticket_service,
+                        gitrello_api_client.as_ref(),
+                        board_repository.id,
+                        issue_id,
+                        issue_number,
+                        ticket.id,
+                    )
+                        .await;
+
+                    match result {
+                        Ok(issue_ticket) => created_issue_tickets.push(issue_ticket),
+                        Err(e) => errors.push(e),
+                    }
+                },
+                Err(e) if !is_retryable(&e) => {
+                    error!(
+                        "Failed to create ticket for issue {} of board repository {}: {}",
+                        issue_id,
+                        board_repository.id,
+                        e,
+                    );
+                    errors.push(e);
+                },
+                Err(e) => {
+                    if let Err(e) = ticket_outbox_service
+                        .enqueue(board_repository, issue_id, issue_number, title, body, &e)
+                        .await
+                    {
+                        error!(
+                            "Failed to enqueue ticket for issue {} of board repository {}: {}",
+                            issue_id,
+                            board_repository.id,
+                            e,
+                        );
+                        errors.push(e);
+                    }
+                }
+            }
+        }
+
+        GITrelloError::aggregate(errors).map(|_| created_issue_tickets)
     }
+
+    // Boards linked to the repository and subscribed to the event, except those that do not accept events
+    // from the given bots
+    async fn get_board_repositories(
+        &self,
+        repository: &Repository,
+        event: &str,
+        actors: &[&GithubUser],
+    ) -> Result<Vec<BoardRepository>, GITrelloError>
+    {
+        let parts = repository.split_full_name();
+
+        let connection = self.state.get_db_connection()?;
+        let actor = BoardRepositoryRepository::new(connection).start();
+
+        let board_repositories: Vec<BoardRepository> = actor
+            .send(GetBoardRepositoriesByRepositoryMessage {
+                repository_id: repository.id,
+                repository_owner: parts.0.to_string(),
+                repository_name: parts.1.to_string(),
+            })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })??;
+
+        Ok(
+            board_repositories
+                .into_iter()
+                .filter(|board_repository| !board_repository.is_read_only)
+                .filter(|board_repository| board_repository.events.iter().any(|e| e == event))
+                .filter(|board_repository| is_allowed_actors(board_repository, actors))
+                .collect()
+        )
+    }
+}
+
+pub fn get_previous_owner_and_name(
+    repository: &Repository,
+    changes: &Option<RepositoryChanges>,
+) -> Option<(String, String)>
+{
+    let changes = changes.as_ref()?;
+    let (repository_owner, repository_name) = repository.split_full_name();
+
+    if let Some(repository_changes) = &changes.repository {
+        return Some((repository_owner.to_string(), repository_changes.name.from.clone()));
+    }
+
+    let previous_owner = changes.owner.as_ref()?;
+    let owner = previous_owner.from.user.as_ref().or(previous_owner.from.organization.as_ref())?;
+    Some((owner.login.clone(), repository_name.to_string()))
+}
+
+// Payloads that do not match the models are rejected with the reason instead of an internal error
+fn parse_webhook_request<T>(event_type: &str, request_body: &[u8]) -> Result<T, GITrelloError>
+    where T: DeserializeOwned
+{
+    serde_json::from_slice::<T>(request_body).map_err(|e| GITrelloError::BadRequest {
+        message: format!("Invalid {} payload: {}", event_type, e),
+    })
+}
+
+// The repository event is always included, GitHub sorts events of a hook so they are sorted here as well
+pub fn get_webhook_events(board_repositories: &[BoardRepository]) -> Vec<String> {
+    let mut events: Vec<String> = board_repositories
+        .iter()
+        .flat_map(|board_repository| board_repository.events.iter().cloned())
+        .chain(iter::once(String::from(EVENT_REPOSITORY)))
+        .collect();
+    events.sort();
+    events.dedup();
+    events
+}
+
+// Bots are ignored unless the board explicitly allows their logins
+pub fn is_allowed_actors(board_repository: &BoardRepository, actors: &[&GithubUser]) -> bool {
+    actors
+        .iter()
+        .filter(|actor| actor.is_bot())
+        .all(|actor| {
+            board_repository.allowed_bot_logins
+                .iter()
+                .any(|login| login.eq_ignore_ascii_case(actor.login.as_str()))
+        })
+}
+
+pub fn format_ticket_comment(comment: &Comment) -> String {
+    format!(
+        "**{}** commented on GitHub ({}):\n\n{}",
+        comment.user.login,
+        comment.html_url,
+        comment.body,
+    )
+}
+
+pub fn format_pull_request_state(pull_request: &PullRequest) -> String {
+    match (pull_request.merged, &pull_request.merged_by) {
+        (true, Some(merged_by)) => format!("Pull request was merged by **{}**", merged_by.login),
+        (true, None) => String::from("Pull request was merged"),
+        (false, _) => String::from("Pull request was closed without merging"),
+    }
+}
+
+#[test]
+fn test_format_ticket_comment() {
+    let comment = Comment {
+        id: 1,
+        html_url: String::from("https://github.com/octocat/hello-world/issues/1#issuecomment-1"),
+        body: String::from("Looks good to me"),
+        user: GithubUser { id: 2, login: String::from("octocat"), user_type: String::from("User") },
+    };
+
+    assert_eq!(
+        format_ticket_comment(&comment),
+        "**octocat** commented on GitHub \
+        (https://github.com/octocat/hello-world/issues/1#issuecomment-1):\n\nLooks good to me",
+    );
+}
+
+#[test]
+fn test_format_pull_request_state() {
+    let mut pull_request = PullRequest {
+        id: 1,
+        number: 2,
+        html_url: String::from("https://github.com/octocat/hello-world/pull/2"),
+        title: String::from("Fix everything"),
+        body: None,
+        draft: false,
+        merged: true,
+        user: GithubUser { id: 3, login: String::from("octocat"), user_type: String::from("User") },
+        merged_by: Some(GithubUser { id: 4, login: String::from("hubot"), user_type: String::from("User") }),
+    };
+    assert_eq!(format_pull_request_state(&pull_request), "Pull request was merged by **hubot**");
+
+    pull_request.merged_by = None;
+    assert_eq!(format_pull_request_state(&pull_request), "Pull request was merged");
+
+    pull_request.merged = false;
+    assert_eq!(format_pull_request_state(&pull_request), "Pull request was closed without merging");
+}
+
+#[test]
+fn test_is_allowed_actors() {
+    let mut board_repository = get_fake_board_repository(1, "backend", &[]);
+    let user = GithubUser { id: 1, login: String::from("octocat"), user_type: String::from("User") };
+    let bot = GithubUser { id: 2, login: String::from("dependabot[bot]"), user_type: String::from("Bot") };
+
+    assert!(is_allowed_actors(&board_repository, &[&user]));
+    assert!(!is_allowed_actors(&board_repository, &[&user, &bot]));
+
+    board_repository.allowed_bot_logins = vec![String::from("Dependabot[bot]")];
+    assert!(is_allowed_actors(&board_repository, &[&user, &bot]));
+}
+
+#[test]
+fn test_get_webhook_events() {
+    let board_repository = get_fake_board_repository(1, "backend", &[EVENT_PUSH, EVENT_ISSUES]);
+    let other_board_repository = get_fake_board_repository(2, "backend", &[EVENT_ISSUES, EVENT_ISSUE_COMMENT]);
+
+    assert_eq!(get_webhook_events(&[]), vec!["repository"]);
+    assert_eq!(get_webhook_events(slice::from_ref(&board_repository)), vec!["issues", "push", "repository"]);
+    assert_eq!(
+        get_webhook_events(&[board_repository, other_board_repository]),
+        vec!["issue_comment", "issues", "push", "repository"],
+    );
+}
+
+#[test]
+fn test_get_previous_owner_and_name() {
+    let repository = Repository {
+        id: 1,
+        full_name: String::from("octo-org/new-name"),
+        default_branch: Some(String::from("main")),
+    };
+
+    assert_eq!(get_previous_owner_and_name(&repository, &None), None);
+
+    let renamed = serde_json::from_str::<RepositoryChanges>(
+        r#"{"repository": {"name": {"from": "old-name"}}}"#,
+    ).ok();
+    assert_eq!(
+        get_previous_owner_and_name(&repository, &renamed),
+        Some((String::from("octo-org"), String::from("old-name"))),
+    );
+
+    let transferred = serde_json::from_str::<RepositoryChanges>(
+        r#"{"owner": {"from": {"user": {"login": "octocat"}}}}"#,
+    ).ok();
+    assert_eq!(
+        get_previous_owner_and_name(&repository, &transferred),
+        Some((String::from("octocat"), String::from("new-name"))),
+    );
+}
+
+#[cfg(test)]
+async fn create_or_update_with_fakes(
+    github: &FakeGitHubAPIClient,
+    repository: FakeGithubWebhookRepository,
+    board_repository: &BoardRepository,
+    repository_name: &str,
+) -> (Result<GithubWebhook, GITrelloError>, Addr<FakeGithubWebhookRepository>)
+{
+    let state = Data::new(get_fake_state(github, &FakeGITrelloAPIClient::default()));
+    let actor = repository.start();
+    let github_webhook_service = GithubWebhookService::with_actor(&state, actor.clone(), Box::new(github.clone()));
+
+    let result = github_webhook_service
+        .create_or_update(board_repository, repository_name, "gitrello")
+        .await;
+    (result, actor)
+}
+
+#[cfg(test)]
+async fn get_webhook_id(actor: &Addr<FakeGithubWebhookRepository>, board_repository_id: i32) -> Option<i64> {
+    actor
+        .send(GetByBoardRepositoryIdMessage { board_repository_id })
+        .await
+        .unwrap()
+        .ok()
+        .map(|github_webhook| github_webhook.webhook_id)
+}
+
+#[actix_rt::test]
+async fn test_create_or_update_creates_webhook() {
+    let github = FakeGitHubAPIClient::default();
+    let board_repository = get_fake_board_repository(1, "backend", &[EVENT_ISSUES]);
+    let repository = FakeGithubWebhookRepository {
+        board_repositories: vec![board_repository.clone()],
+        ..Default::default()
+    };
+
+    let (result, actor) = create_or_update_with_fakes(&github, repository, &board_repository, "backend").await;
+
+    let webhook_ids = github.get_webhook_ids("backend", "gitrello");
+    assert_eq!(result.map(|github_webhook| github_webhook.webhook_id).ok(), webhook_ids.first().copied());
+    assert_eq!(get_webhook_id(&actor, 1).await, webhook_ids.first().copied());
+    assert_eq!(
+        github.get_calls(),
+        vec![GitHubAPICall::CreateWebhook {
+            repository: String::from("gitrello/backend"),
+            events: vec![String::from("issues"), String::from("repository")],
+        }],
+    );
+}
+
+#[actix_rt::test]
+async fn test_create_or_update_shares_existing_webhook() {
+    let github = FakeGitHubAPIClient::default();
+    let webhook_id = github.add_webhook("backend", "gitrello", vec![String::from("issues")]);
+
+    let board_repository = get_fake_board_repository(2, "backend", &[EVENT_PUSH]);
+    let mut repository = FakeGithubWebhookRepository {
+        board_repositories: vec![get_fake_board_repository(1, "backend", &[EVENT_ISSUES]), board_repository.clone()],
+        ..Default::default()
+    };
+    repository.add_github_webhook(1, webhook_id);
+
+    let (result, actor) = create_or_update_with_fakes(&github, repository, &board_repository, "backend").await;
+
+    let github_webhook = result.unwrap();
+    assert_eq!(github_webhook.webhook_id, webhook_id);
+    assert_eq!(github_webhook.secret, format!("secret-{}", webhook_id));
+    assert_eq!(get_webhook_id(&actor, 2).await, Some(webhook_id));
+    assert_eq!(github.get_webhook_ids("backend", "gitrello"), vec![webhook_id]);
+    assert_eq!(
+        github.get_calls(),
+        vec![GitHubAPICall::UpdateWebhookEvents {
+            repository: String::from("gitrello/backend"),
+            webhook_id,
+            events: vec![String::from("issues"), String::from("push"), String::from("repository")],
+        }],
+    );
+}
+
+#[actix_rt::test]
+async fn test_create_or_update_deletes_unused_webhook() {
+    let github = FakeGitHubAPIClient::default();
+    let old_webhook_id = github.add_webhook("backend", "gitrello", vec![String::from("issues")]);
+
+    let board_repository = get_fake_board_repository(1, "backend", &[EVENT_ISSUES]);
+    let mut repository = FakeGithubWebhookRepository {
+        board_repositories: vec![board_repository.clone()],
+        ..Default::default()
+    };
+    repository.add_github_webhook(1, old_webhook_id);
+
+    let (result, actor) = create_or_update_with_fakes(&github, repository, &board_repository, "frontend").await;
+
+    let webhook_ids = github.get_webhook_ids("frontend", "gitrello");
+    assert_eq!(result.map(|github_webhook| github_webhook.webhook_id).ok(), webhook_ids.first().copied());
+    assert_eq!(get_webhook_id(&actor, 1).await, webhook_ids.first().copied());
+    assert!(github.get_webhook_ids("backend", "gitrello").is_empty());
+    assert_eq!(
+        github.get_calls(),
+        vec![
+            GitHubAPICall::DeleteWebhook { repository: String::from("gitrello/backend"), webhook_id: old_webhook_id },
+            GitHubAPICall::CreateWebhook {
+                repository: String::from("gitrello/frontend"),
+                events: vec![String::from("issues"), String::from("repository")],
+            },
+        ],
+    );
+}
+
+#[actix_rt::test]
+async fn test_create_or_update_keeps_shared_webhook() {
+    let github = FakeGitHubAPIClient::default();
+    let old_webhook_id = github.add_webhook("backend", "gitrello", vec![String::from("issues"), String::from("push")]);
+    let webhook_id = github.add_webhook("frontend", "gitrello", vec![String::from("issue_comment")]);
+
+    let board_repository = get_fake_board_repository(1, "backend", &[EVENT_PUSH]);
+    let mut repository = FakeGithubWebhookRepository {
+        board_repositories: vec![
+            board_repository.clone(),
+            get_fake_board_repository(2, "backend", &[EVENT_ISSUES]),
+            get_fake_board_repository(3, "frontend", &[EVENT_ISSUE_COMMENT]),
+        ],
+        ..Default::default()
+    };
+    repository.add_github_webhook(1, old_webhook_id);
+    repository.add_github_webhook(2, old_webhook_id);
+    repository.add_github_webhook(3, webhook_id);
+
+    let (result, actor) = create_or_update_with_fakes(&github, repository, &board_repository, "frontend").await;
+
+    assert_eq!(result.map(|github_webhook| github_webhook.webhook_id).ok(), Some(webhook_id));
+    assert_eq!(get_webhook_id(&actor, 1).await, Some(webhook_id));
+    assert_eq!(get_webhook_id(&actor, 2).await, Some(old_webhook_id));
+    assert_eq!(github.get_webhook_ids("backend", "gitrello"), vec![old_webhook_id]);
+    // Board repository 1 is renamed after webhooks are updated, so it still counts as "backend" here
+    assert_eq!(
+        github.get_calls(),
+        vec![
+            GitHubAPICall::UpdateWebhookEvents {
+                repository: String::from("gitrello/frontend"),
+                webhook_id,
+                events: vec![String::from("issue_comment"), String::from("push"), String::from("repository")],
+            },
+            GitHubAPICall::UpdateWebhookEvents {
+                repository: String::from("gitrello/backend"),
+                webhook_id: old_webhook_id,
+                events: vec![String::from("issues"), String::from("repository")],
+            },
+        ],
+    );
+}
+
+#[actix_rt::test]
+async fn test_create_or_update_keeps_webhook_when_github_fails() {
+    let github = FakeGitHubAPIClient::default();
+    github.fail("create_webhook");
+
+    let board_repository = get_fake_board_repository(1, "backend", &[EVENT_ISSUES]);
+    let repository = FakeGithubWebhookRepository {
+        board_repositories: vec![board_repository.clone()],
+        ..Default::default()
+    };
+
+    let (result, actor) = create_or_update_with_fakes(&github, repository, &board_repository, "backend").await;
+
+    assert!(matches!(result, Err(GITrelloError::GitHubAPIClientError { .. })));
+    assert_eq!(get_webhook_id(&actor, 1).await, None);
+}
+
+#[actix_rt::test]
+async fn test_create_or_update_relinks_board_repository_without_webhook() {
+    let github = FakeGitHubAPIClient::default();
+    let board_repository = get_fake_board_repository(1, "backend", &[EVENT_ISSUES]);
+    let repository = FakeGithubWebhookRepository {
+        board_repositories: vec![board_repository.clone()],
+        ..Default::default()
+    };
+
+    let (result, _) = create_or_update_with_fakes(&github, repository, &board_repository, "frontend").await;
+
+    assert!(matches!(result, Err(GITrelloError::NotFound { .. })));
+    assert!(github.get_webhook_ids("frontend", "gitrello").is_empty());
 }
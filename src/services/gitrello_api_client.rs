@@ -1,20 +1,77 @@
+use async_trait::async_trait;
 use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
 use reqwest::{Client, Error, Response, StatusCode};
 use serde::de::DeserializeOwned;
 use serde_json;
 
 use crate::errors::GITrelloError;
+use crate::state::State;
 use crate::value_objects::gitrello_api::{
-    APIError, Permissions, GetBoardPermissionsRequest, CreateTicketRequest,
+    APIError, Permissions, GetBoardPermissionsRequest, CreateTicketRequest, Ticket, TicketComment,
+    TicketCommentRequest, UpdateTicketRequest,
 };
 
-pub struct GITRelloAPIClient<'a> {
-    gitrello_url: &'a str,
+// Services get clients through State, so tests can replace GITrello with a fake
+#[async_trait(?Send)]
+pub trait GITrelloAPI {
+    async fn get_board_permissions(&self, user_id: i64, board_id: i64) -> Result<Permissions, GITrelloError>;
+
+    async fn create_ticket(
+        &self,
+        board_id: i64,
+        title: &str,
+        body: &str,
+        idempotency_key: Option<&str>,
+    ) -> Result<Ticket, GITrelloError>;
+
+    async fn update_ticket(&self, ticket_id: i64, title: &str, body: &str) -> Result<Ticket, GITrelloError>;
+
+    async fn close_ticket(&self, ticket_id: i64) -> Result<Ticket, GITrelloError>;
+
+    async fn reopen_ticket(&self, ticket_id: i64) -> Result<Ticket, GITrelloError>;
+
+    async fn archive_ticket(&self, ticket_id: i64) -> Result<Ticket, GITrelloError>;
+
+    async fn create_ticket_comment(&self, ticket_id: i64, body: &str) -> Result<TicketComment, GITrelloError>;
+
+    async fn update_ticket_comment(
+        &self,
+        ticket_id: i64,
+        ticket_comment_id: i64,
+        body: &str,
+    ) -> Result<TicketComment, GITrelloError>;
+
+    async fn delete_ticket_comment(&self, ticket_id: i64, ticket_comment_id: i64) -> Result<(), GITrelloError>;
+}
+
+pub trait GITrelloAPIClientFactory: Send + Sync {
+    fn create(&self, state: &State, access_token: Option<&str>) -> Box<dyn GITrelloAPI>;
+}
+
+pub struct HttpGITrelloAPIClientFactory;
+
+impl GITrelloAPIClientFactory for HttpGITrelloAPIClientFactory {
+    fn create(&self, state: &State, access_token: Option<&str>) -> Box<dyn GITrelloAPI> {
+        match access_token {
+            Some(access_token) => Box::new(GITRelloAPIClient::with_access_token(state, access_token)),
+            None => Box::new(GITRelloAPIClient::new(state)),
+        }
+    }
+}
+
+// Authenticated with the token GITrello issued to this service
+pub fn get_gitrello_api_client(state: &State) -> Box<dyn GITrelloAPI> {
+    state.gitrello_api_client_factory.create(state, Some(state.gitrello_access_token.as_str()))
+}
+
+pub struct GITRelloAPIClient {
+    gitrello_url: String,
+    client: Client,
     headers: HeaderMap,
 }
 
-impl<'a> GITRelloAPIClient<'a> {
-    pub fn new(gitrello_url: &'a str) -> Self {
+impl GITRelloAPIClient {
+    pub fn new(state: &State) -> Self {
         let user_agent_header_value = HeaderValue::from_str(
             "GITRello GitHub Integration Service",
             )
@@ -23,14 +80,14 @@ impl<'a> GITRelloAPIClient<'a> {
         let mut headers = HeaderMap::new();
         headers.insert(USER_AGENT, user_agent_header_value);
 
-        Self { headers, gitrello_url }
+        Self { headers, gitrello_url: state.gitrello_url.clone(), client: state.http_client.clone() }
     }
 
-    pub fn with_access_token(gitrello_url: &'a str, access_token: &str) -> Self {
+    pub fn with_access_token(state: &State, access_token: &str) -> Self {
         let authorization_header_value = HeaderValue::from_str(access_token)
             .expect("Authorization header should be valid");
 
-        let mut service = Self::new(gitrello_url);
+        let mut service = Self::new(state);
         service.headers.insert("GITHUB_INTEGRATION_SERVICE_TOKEN", authorization_header_value);
         service
     }
@@ -54,11 +111,10 @@ impl<'a> GITRelloAPIClient<'a> {
                     // Response with valid code
                     json
                         .map(|json| Some(json))
-                        .map_err(|source| GITrelloError::GitHubAPIClientError {
-                            message: source.to_string(),
-                        })
+                        .map_err(GITrelloError::from_http_error)
                 }
                 else {
+                    let status_code = response.status().as_u16();
                     let response_string = response.text().await;
                     match response_string {
                         Ok(response_string) => {
@@ -70,31 +126,58 @@ impl<'a> GITRelloAPIClient<'a> {
                                 Ok(api_error) => {
                                     Err(GITrelloError::GITrelloAPIClientError {
                                         message: api_error.error_message,
+                                        status_code,
                                     })
                                 },
                                 // Response with invalid code can not be deserialized into APIError
                                 _ => {
                                     Err(GITrelloError::GITrelloAPIClientError {
                                         message: response_string,
+                                        status_code,
                                     })
                                 }
                             }
                         },
                         // Response with invalid code can not be read into String
                         Err(e) => {
-                            Err(GITrelloError::GITrelloAPIClientError { message: e.to_string() })
+                            Err(GITrelloError::GITrelloAPIClientError { message: e.to_string(), status_code })
                         }
                     }
                 }
             }
             // HTTP Request failed
             Err(e) => {
-                Err(GITrelloError::HttpRequestError {source: e})
+                Err(GITrelloError::from_http_error(e))
             }
         }
     }
 
-    pub async fn get_board_permissions(
+    async fn patch_ticket(
+        &self,
+        ticket_id: i64,
+        data: &UpdateTicketRequest,
+    ) -> Result<Ticket, GITrelloError>
+    {
+        let url = format!("{}/oauth/api/v1/tickets/{}", self.gitrello_url, ticket_id);
+
+        let response = self.client
+            .patch(&url)
+            .json(data)
+            .headers(self.headers.clone())
+            .send()
+            .await;
+
+        let result = self.process_response::<Ticket>(response, StatusCode::OK).await;
+        match result {
+            Ok(result) => Ok(result.expect("can not be None")),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl GITrelloAPI for GITRelloAPIClient {
+    async fn get_board_permissions(
         &self,
         user_id: i64,
         board_id: i64,
@@ -102,7 +185,7 @@ impl<'a> GITRelloAPIClient<'a> {
     {
         let url = format!("{}/{}", self.gitrello_url, "api/v1/board-permissions");
 
-        let response = Client::new()
+        let response = self.client
             .get(&url)
             .json(&GetBoardPermissionsRequest {board_id, user_id})
             .headers(self.headers.clone())
@@ -116,26 +199,172 @@ impl<'a> GITRelloAPIClient<'a> {
         }
     }
 
-    pub async fn create_ticket(
+    async fn create_ticket(
         &self,
         board_id: i64,
         title: &str,
         body: &str,
-    ) -> Result<(), GITrelloError>
+        idempotency_key: Option<&str>,
+    ) -> Result<Ticket, GITrelloError>
     {
         let url = format!("{}/{}", self.gitrello_url, "oauth/api/v1/tickets");
 
-        let response = Client::new()
+        let mut request = self.client
             .post(&url)
             .json(&CreateTicketRequest {board_id, title: title.to_string(), body: body.to_string() })
+            .headers(self.headers.clone());
+
+        // Lets GITrello recognize a retried request, which may have been applied before timing out
+        if let Some(idempotency_key) = idempotency_key {
+            request = request.header("Idempotency-Key", idempotency_key);
+        }
+
+        let response = request.send().await;
+
+        let result = self.process_response::<Ticket>(response, StatusCode::OK).await;
+        match result {
+            Ok(result) => Ok(result.expect("can not be None")),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn update_ticket(
+        &self,
+        ticket_id: i64,
+        title: &str,
+        body: &str,
+    ) -> Result<Ticket, GITrelloError>
+    {
+        self
+            .patch_ticket(
+                ticket_id,
+                &UpdateTicketRequest {
+                    title: Some(title.to_string()),
+                    body: Some(body.to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+    }
+
+    async fn close_ticket(&self, ticket_id: i64) -> Result<Ticket, GITrelloError> {
+        self
+            .patch_ticket(ticket_id, &UpdateTicketRequest { is_closed: Some(true), ..Default::default() })
+            .await
+    }
+
+    async fn reopen_ticket(&self, ticket_id: i64) -> Result<Ticket, GITrelloError> {
+        self
+            .patch_ticket(ticket_id, &UpdateTicketRequest { is_closed: Some(false), ..Default::default() })
+            .await
+    }
+
+    async fn archive_ticket(&self, ticket_id: i64) -> Result<Ticket, GITrelloError> {
+        self
+            .patch_ticket(ticket_id, &UpdateTicketRequest { is_archived: Some(true), ..Default::default() })
+            .await
+    }
+
+    async fn create_ticket_comment(
+        &self,
+        ticket_id: i64,
+        body: &str,
+    ) -> Result<TicketComment, GITrelloError>
+    {
+        let url = format!("{}/oauth/api/v1/tickets/{}/comments", self.gitrello_url, ticket_id);
+
+        let response = self.client
+            .post(&url)
+            .json(&TicketCommentRequest { body: body.to_string() })
+            .headers(self.headers.clone())
+            .send()
+            .await;
+
+        let result = self.process_response::<TicketComment>(response, StatusCode::CREATED).await;
+        match result {
+            Ok(result) => Ok(result.expect("can not be None")),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn update_ticket_comment(
+        &self,
+        ticket_id: i64,
+        ticket_comment_id: i64,
+        body: &str,
+    ) -> Result<TicketComment, GITrelloError>
+    {
+        let url = format!(
+            "{}/oauth/api/v1/tickets/{}/comments/{}",
+            self.gitrello_url,
+            ticket_id,
+            ticket_comment_id,
+        );
+
+        let response = self.client
+            .patch(&url)
+            .json(&TicketCommentRequest { body: body.to_string() })
             .headers(self.headers.clone())
             .send()
             .await;
 
-        let result = self.process_response::<()>(response, StatusCode::OK).await;
+        let result = self.process_response::<TicketComment>(response, StatusCode::OK).await;
+        match result {
+            Ok(result) => Ok(result.expect("can not be None")),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn delete_ticket_comment(
+        &self,
+        ticket_id: i64,
+        ticket_comment_id: i64,
+    ) -> Result<(), GITrelloError>
+    {
+        let url = format!(
+            "{}/oauth/api/v1/tickets/{}/comments/{}",
+            self.gitrello_url,
+            ticket_id,
+            ticket_comment_id,
+        );
+
+        let response = self.client
+            .delete(&url)
+            .headers(self.headers.clone())
+            .send()
+            .await;
+
+        let result = self.process_response::<()>(response, StatusCode::NO_CONTENT).await;
         match result {
             Ok(_) => Ok(()),
             Err(e) => Err(e),
         }
     }
 }
+
+#[actix_rt::test]
+async fn test_get_gitrello_api_client() {
+    use crate::services::fakes::{get_fake_state, FakeGITrelloAPIClient, FakeGitHubAPIClient, GITrelloAPICall};
+
+    let gitrello = FakeGITrelloAPIClient::default();
+    gitrello.fail("close_ticket");
+
+    let state = get_fake_state(&FakeGitHubAPIClient::default(), &gitrello);
+    let gitrello_api_client = get_gitrello_api_client(&state);
+
+    let ticket = gitrello_api_client.create_ticket(1, "Title", "Body", Some("key")).await;
+    assert_eq!(ticket.map(|ticket| ticket.id).ok(), Some(1));
+    assert!(gitrello_api_client.close_ticket(1).await.is_err());
+    assert_eq!(
+        gitrello.get_calls(),
+        vec![
+            GITrelloAPICall::CreateTicket {
+                board_id: 1,
+                title: String::from("Title"),
+                body: String::from("Body"),
+                idempotency_key: Some(String::from("key")),
+            },
+            GITrelloAPICall::CloseTicket { ticket_id: 1 },
+        ],
+    );
+}
@@ -0,0 +1,280 @@
+use std::slice;
+
+use actix::{Actor, Addr};
+use actix_web::web::Data;
+use chrono::Utc;
+
+use crate::errors::GITrelloError;
+use crate::models::board_repository::BoardRepository;
+use crate::models::import_job::{
+    get_stale_before, ImportJob, NewImportJob, UpdateImportJob, STATUS_FAILED, STATUS_FINISHED, STATUS_PENDING,
+    STATUS_RUNNING,
+};
+use crate::services::github_api_client::{get_github_api_client, ISSUES_PER_PAGE};
+use crate::services::github_profile_service::GithubProfileService;
+use crate::services::github_webhook_service::{is_allowed_actors, GithubWebhookProcessingService};
+use crate::services::issue_filter_service::{matches_filters, IssueFilterService};
+use crate::services::issue_ticket_service::IssueTicketService;
+use crate::services::ticket_template::TicketTemplateContext;
+use crate::services::repositories::board_repository::{
+    BoardRepositoryRepository, GetBoardRepositoryByIdMessage,
+};
+use crate::services::repositories::import_job::{
+    CreateImportJobMessage, GetImportJobByIdMessage, GetLatestImportJobByBoardRepositoryIdMessage,
+    GetNextImportJobByStatusMessage, ImportJobRepository, ResetStaleImportJobsMessage, UpdateImportJobMessage,
+};
+use crate::state::State;
+
+const MAX_LISTED_FAILURES: usize = 20;
+
+pub struct ImportJobService<'a> {
+    state: &'a Data<State>,
+    actor: Addr<ImportJobRepository>,
+}
+
+impl<'a> ImportJobService<'a> {
+    pub fn new(state: &'a Data<State>) -> Result<Self, GITrelloError> {
+        let connection = state.get_db_connection()?;
+        let actor = ImportJobRepository::new(connection).start();
+        Ok(Self { state, actor })
+    }
+
+    // Only one import per board repository runs at a time, the active one is returned instead of a new one
+    pub async fn enqueue(&self, board_repository: &BoardRepository) -> Result<(ImportJob, bool), GITrelloError> {
+        let latest_import_job: Option<ImportJob> = self.actor
+            .send(GetLatestImportJobByBoardRepositoryIdMessage { board_repository_id: board_repository.id })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })??;
+
+        if let Some(latest_import_job) = latest_import_job {
+            if latest_import_job.is_active(Utc::now()) {
+                return Ok((latest_import_job, false));
+            }
+        }
+
+        let import_job = self.actor
+            .send(CreateImportJobMessage {
+                data: NewImportJob {
+                    board_repository_id: board_repository.id,
+                    status: String::from(STATUS_PENDING),
+                },
+            })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })??;
+
+        Ok((import_job, true))
+    }
+
+    pub async fn get_by_id(&self, board_repository: &BoardRepository, id: i32) -> Result<ImportJob, GITrelloError> {
+        let import_job: ImportJob = self.actor
+            .send(GetImportJobByIdMessage { id })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })??;
+
+        if import_job.board_repository_id != board_repository.id {
+            return Err(GITrelloError::NotFound { message: format!("import_job {} does not exist", id) });
+        }
+
+        Ok(import_job)
+    }
+
+    pub async fn run_next(&self) -> Result<(), GITrelloError> {
+        // Jobs interrupted by a crash or restart are started over, issues imported before are skipped
+        let reset_count = self.actor
+            .send(ResetStaleImportJobsMessage {
+                status: String::from(STATUS_RUNNING),
+                updated_before: get_stale_before(Utc::now()),
+                new_status: String::from(STATUS_PENDING),
+            })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })??;
+        if reset_count > 0 {
+            warn!("{} interrupted import jobs have been moved back to the queue", reset_count);
+        }
+
+        let import_job: Option<ImportJob> = self.actor
+            .send(GetNextImportJobByStatusMessage { status: String::from(STATUS_PENDING) })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })??;
+
+        let import_job = match import_job {
+            Some(import_job) => import_job,
+            None => return Ok(()),
+        };
+
+        let import_job = self
+            .update(import_job, UpdateImportJob { status: Some(String::from(STATUS_RUNNING)), ..Default::default() })
+            .await?;
+
+        let data = match self.import(import_job.clone()).await {
+            Ok(import_job) => {
+                info!(
+                    "Import job {} has finished: {} imported, {} skipped",
+                    import_job.id,
+                    import_job.imported,
+                    import_job.skipped,
+                );
+                UpdateImportJob {
+                    status: Some(String::from(STATUS_FINISHED)),
+                    finished_at: Some(Some(Utc::now())),
+                    ..Default::default()
+                }
+            },
+            Err(e) => {
+                error!("Import job {} has failed: {}", import_job.id, e);
+                UpdateImportJob {
+                    status: Some(String::from(STATUS_FAILED)),
+                    error: Some(Some(e.to_string())),
+                    finished_at: Some(Some(Utc::now())),
+                    ..Default::default()
+                }
+            },
+        };
+
+        self.update(import_job, data).await?;
+        Ok(())
+    }
+
+    // Pages through open issues, progress is saved after every page so it can be polled
+    async fn import(&self, mut import_job: ImportJob) -> Result<ImportJob, GITrelloError> {
+        let connection = self.state.get_db_connection()?;
+        let board_repository: BoardRepository = BoardRepositoryRepository::new(connection)
+            .start()
+            .send(GetBoardRepositoryByIdMessage { id: import_job.board_repository_id })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })??;
+
+        let github_profile_service = GithubProfileService::new(self.state)?;
+        let github_profile = github_profile_service
+            .get_by_id(board_repository.github_profile_id)
+            .await?;
+        let github_api_client = get_github_api_client(self.state, &github_profile);
+
+        let issue_filter_service = IssueFilterService::new(self.state)?;
+        let issue_filters = issue_filter_service
+            .get_by_board_repositories(slice::from_ref(&board_repository))
+            .await?;
+
+        let issue_ticket_service = IssueTicketService::new(self.state)?;
+        let processing_service = GithubWebhookProcessingService::new(self.state);
+
+        let repository = format!("{}/{}", board_repository.repository_owner, board_repository.repository_name);
+
+        let (mut total, mut imported, mut skipped) = (0, 0, 0);
+        let mut failures = vec![];
+        let mut page = 1;
+        loop {
+            let issues = github_api_client
+                .get_open_issues(
+                    board_repository.repository_name.as_str(),
+                    board_repository.repository_owner.as_str(),
+                    page,
+                )
+                .await?;
+            let is_last_page = issues.len() < ISSUES_PER_PAGE;
+
+            for issue in issues.iter().filter(|issue| issue.pull_request.is_none()) {
+                total += 1;
+
+                if !matches_filters(&issue_filters, issue) || !is_allowed_actors(&board_repository, &[&issue.user]) {
+                    skipped += 1;
+                    continue;
+                }
+
+                let issue_tickets = issue_ticket_service
+                    .get_by_board_repositories_and_issue_id(slice::from_ref(&board_repository), issue.id)
+                    .await;
+                let issue_tickets = match issue_tickets {
+                    Ok(issue_tickets) if !issue_tickets.is_empty() => {
+                        skipped += 1;
+                        continue;
+                    },
+                    Ok(issue_tickets) => issue_tickets,
+                    Err(e) => {
+                        failures.push(format!("#{}: {}", issue.number, e));
+                        continue;
+                    },
+                };
+
+                // A failing issue does not stop the import, it is reported in the error of the job
+                let result = processing_service
+                    .create_tickets(
+                        vec![board_repository.clone()],
+                        &issue_tickets,
+                        issue.id,
+                        issue.number,
+                        &TicketTemplateContext::from_issue(repository.as_str(), issue),
+                    )
+                    .await;
+                match result {
+                    Ok(created_issue_tickets) if !created_issue_tickets.is_empty() => imported += 1,
+                    Ok(_) => failures.push(format!("#{}: queued for retry", issue.number)),
+                    Err(e) => {
+                        error!("Import job {} failed to import issue #{}: {}", import_job.id, issue.number, e);
+                        failures.push(format!("#{}: {}", issue.number, e));
+                    },
+                }
+            }
+
+            import_job = self
+                .update(
+                    import_job,
+                    UpdateImportJob {
+                        total: Some(total),
+                        imported: Some(imported),
+                        skipped: Some(skipped),
+                        error: Some(format_import_failures(&failures)),
+                        ..Default::default()
+                    },
+                )
+                .await?;
+
+            if is_last_page {
+                return Ok(import_job);
+            }
+            page += 1;
+        }
+    }
+
+    // Every update doubles as a heartbeat of the running job
+    async fn update(&self, import_job: ImportJob, data: UpdateImportJob) -> Result<ImportJob, GITrelloError> {
+        let data = UpdateImportJob { updated_at: Some(Utc::now()), ..data };
+
+        self.actor
+            .send(UpdateImportJobMessage { import_job, data })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })?
+    }
+}
+
+// Only the first failures are listed, the error is meant to be read by people
+pub fn format_import_failures(failures: &[String]) -> Option<String> {
+    if failures.is_empty() {
+        return None;
+    }
+
+    let mut error = format!(
+        "Failed to import {} issues: {}",
+        failures.len(),
+        failures.iter().take(MAX_LISTED_FAILURES).cloned().collect::<Vec<String>>().join("; "),
+    );
+    if failures.len() > MAX_LISTED_FAILURES {
+        error.push_str(format!("; and {} more", failures.len() - MAX_LISTED_FAILURES).as_str());
+    }
+
+    Some(error)
+}
+
+#[test]
+fn test_format_import_failures() {
+    assert_eq!(format_import_failures(&[]), None);
+    assert_eq!(
+        format_import_failures(&[String::from("#1: Not Found"), String::from("#2: queued for retry")]),
+        Some(String::from("Failed to import 2 issues: #1: Not Found; #2: queued for retry")),
+    );
+
+    let failures: Vec<String> = (1..=MAX_LISTED_FAILURES + 2).map(|number| format!("#{}: Not Found", number)).collect();
+    let error = format_import_failures(&failures).unwrap();
+    assert!(error.starts_with(format!("Failed to import {} issues: #1: Not Found;", failures.len()).as_str()));
+    assert!(error.ends_with("; and 2 more"));
+}
@@ -0,0 +1,54 @@
+use std::time::Duration;
+
+use actix::{Actor, ActorFuture, AsyncContext, Context, WrapFuture};
+use actix_web::web::Data;
+
+use crate::services::import_job_service::ImportJobService;
+use crate::state::State;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+pub struct ImportJobWorker {
+    state: Data<State>,
+    is_running: bool,
+}
+
+impl ImportJobWorker {
+    pub fn new(state: Data<State>) -> Self {
+        Self { state, is_running: false }
+    }
+
+    fn run_next(&mut self, ctx: &mut Context<Self>) {
+        // Previous import job is still running
+        if self.is_running {
+            return;
+        }
+        self.is_running = true;
+
+        let state = self.state.clone();
+        let future = async move {
+            let result = match ImportJobService::new(&state) {
+                Ok(import_job_service) => import_job_service.run_next().await,
+                Err(e) => Err(e),
+            };
+
+            if let Err(e) = result {
+                error!("Failed to run import job: {}", e);
+            }
+        };
+
+        ctx.spawn(
+            future
+                .into_actor(self)
+                .map(|_, worker, _ctx| worker.is_running = false),
+        );
+    }
+}
+
+impl Actor for ImportJobWorker {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        ctx.run_interval(POLL_INTERVAL, |worker, ctx| worker.run_next(ctx));
+    }
+}
@@ -0,0 +1,229 @@
+use actix::{Actor, Addr};
+use actix_web::web::Data;
+use regex::Regex;
+
+use crate::errors::GITrelloError;
+use crate::models::board_repository::BoardRepository;
+use crate::models::issue_filter::{
+    IssueFilter, NewIssueFilter, KINDS, KIND_EXCLUDE_AUTHOR, KIND_EXCLUDE_LABEL, KIND_INCLUDE_AUTHOR,
+    KIND_INCLUDE_LABEL, KIND_TITLE_PATTERN,
+};
+use crate::services::repositories::issue_filter::{
+    CreateIssueFilterMessage, DeleteIssueFilterMessage, GetIssueFilterByIdMessage,
+    GetIssueFiltersByBoardRepositoryIdsMessage, IssueFilterRepository, UpdateIssueFilterMessage,
+};
+use crate::state::State;
+use crate::value_objects::github_api::Issue;
+
+pub struct IssueFilterService {
+    actor: Addr<IssueFilterRepository>,
+}
+
+impl IssueFilterService {
+    pub fn new(state: &Data<State>) -> Result<Self, GITrelloError> {
+        let connection = state.get_db_connection()?;
+        let actor = IssueFilterRepository::new(connection).start();
+        Ok(Self { actor })
+    }
+
+    pub async fn get_by_board_repositories(
+        &self,
+        board_repositories: &[BoardRepository],
+    ) -> Result<Vec<IssueFilter>, GITrelloError>
+    {
+        let board_repository_ids = board_repositories
+            .iter()
+            .map(|board_repository| board_repository.id)
+            .collect();
+
+        self.actor
+            .send(GetIssueFiltersByBoardRepositoryIdsMessage { board_repository_ids })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })?
+    }
+
+    pub async fn create(
+        &self,
+        board_repository: &BoardRepository,
+        kind: &str,
+        value: &str,
+    ) -> Result<IssueFilter, GITrelloError>
+    {
+        validate(kind, value)?;
+
+        self.actor
+            .send(CreateIssueFilterMessage {
+                data: NewIssueFilter {
+                    board_repository_id: board_repository.id,
+                    kind: kind.to_string(),
+                    value: value.to_string(),
+                },
+            })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })?
+    }
+
+    pub async fn update(
+        &self,
+        board_repository: &BoardRepository,
+        id: i32,
+        kind: &str,
+        value: &str,
+    ) -> Result<IssueFilter, GITrelloError>
+    {
+        validate(kind, value)?;
+        let issue_filter = self.get_by_id(board_repository, id).await?;
+
+        self.actor
+            .send(UpdateIssueFilterMessage {
+                issue_filter,
+                kind: kind.to_string(),
+                value: value.to_string(),
+            })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })?
+    }
+
+    pub async fn delete(&self, board_repository: &BoardRepository, id: i32) -> Result<(), GITrelloError> {
+        self.get_by_id(board_repository, id).await?;
+
+        self.actor
+            .send(DeleteIssueFilterMessage { id })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })?
+    }
+
+    async fn get_by_id(&self, board_repository: &BoardRepository, id: i32) -> Result<IssueFilter, GITrelloError> {
+        let issue_filter: IssueFilter = self.actor
+            .send(GetIssueFilterByIdMessage { id })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })??;
+
+        if issue_filter.board_repository_id != board_repository.id {
+            return Err(GITrelloError::NotFound {
+                message: format!("issue_filter {} does not exist", id),
+            });
+        }
+
+        Ok(issue_filter)
+    }
+}
+
+fn validate(kind: &str, value: &str) -> Result<(), GITrelloError> {
+    if !KINDS.contains(&kind) {
+        return Err(GITrelloError::BadRequest {
+            message: format!("{} is not a valid filter kind, expected one of {}", kind, KINDS.join(", ")),
+        });
+    }
+
+    if value.is_empty() {
+        return Err(GITrelloError::BadRequest { message: String::from("Filter value can not be empty") });
+    }
+
+    if kind == KIND_TITLE_PATTERN {
+        Regex::new(value).map_err(|e| GITrelloError::BadRequest { message: e.to_string() })?;
+    }
+
+    Ok(())
+}
+
+// Exclusions win over inclusions. Every kind of inclusion that is configured has to match.
+pub fn matches_filters(issue_filters: &[IssueFilter], issue: &Issue) -> bool {
+    let values = |kind: &str| -> Vec<&str> {
+        issue_filters
+            .iter()
+            .filter(|issue_filter| issue_filter.kind == kind)
+            .map(|issue_filter| issue_filter.value.as_str())
+            .collect()
+    };
+
+    let has_label = |label: &&str| issue.labels.iter().any(|issue_label| issue_label.name == *label);
+    let is_author = |login: &&str| issue.user.login.eq_ignore_ascii_case(login);
+    let matches_title = |pattern: &&str| {
+        Regex::new(pattern)
+            .map(|regex| regex.is_match(issue.title.as_str()))
+            .unwrap_or(false)
+    };
+
+    if values(KIND_EXCLUDE_LABEL).iter().any(has_label) ||
+            values(KIND_EXCLUDE_AUTHOR).iter().any(is_author) {
+        return false;
+    }
+
+    let include_labels = values(KIND_INCLUDE_LABEL);
+    let include_authors = values(KIND_INCLUDE_AUTHOR);
+    let title_patterns = values(KIND_TITLE_PATTERN);
+
+    (include_labels.is_empty() || include_labels.iter().any(has_label)) &&
+        (include_authors.is_empty() || include_authors.iter().any(is_author)) &&
+        (title_patterns.is_empty() || title_patterns.iter().any(matches_title))
+}
+
+#[cfg(test)]
+fn get_test_issue() -> Issue {
+    use crate::value_objects::github_api::{GithubUser, Label};
+
+    Issue {
+        id: 1,
+        number: 2,
+        html_url: String::from("https://github.com/octocat/hello-world/issues/2"),
+        title: String::from("[Bug] Login page is broken"),
+        body: None,
+        user: GithubUser { id: 3, login: String::from("Octocat"), user_type: String::from("User") },
+        labels: vec![Label { name: String::from("bug") }, Label { name: String::from("frontend") }],
+        pull_request: None,
+    }
+}
+
+#[cfg(test)]
+fn get_test_issue_filter(kind: &str, value: &str) -> IssueFilter {
+    IssueFilter { id: 1, board_repository_id: 1, kind: kind.to_string(), value: value.to_string() }
+}
+
+#[test]
+fn test_matches_filters_without_filters() {
+    assert!(matches_filters(&[], &get_test_issue()));
+}
+
+#[test]
+fn test_matches_filters_labels() {
+    let issue = get_test_issue();
+
+    assert!(matches_filters(&[get_test_issue_filter(KIND_INCLUDE_LABEL, "bug")], &issue));
+    assert!(!matches_filters(&[get_test_issue_filter(KIND_INCLUDE_LABEL, "question")], &issue));
+    assert!(!matches_filters(&[get_test_issue_filter(KIND_EXCLUDE_LABEL, "frontend")], &issue));
+    assert!(
+        !matches_filters(
+            &[
+                get_test_issue_filter(KIND_INCLUDE_LABEL, "bug"),
+                get_test_issue_filter(KIND_EXCLUDE_LABEL, "frontend"),
+            ],
+            &issue,
+        ),
+    );
+}
+
+#[test]
+fn test_matches_filters_authors() {
+    let issue = get_test_issue();
+
+    assert!(matches_filters(&[get_test_issue_filter(KIND_INCLUDE_AUTHOR, "octocat")], &issue));
+    assert!(!matches_filters(&[get_test_issue_filter(KIND_INCLUDE_AUTHOR, "hubot")], &issue));
+    assert!(!matches_filters(&[get_test_issue_filter(KIND_EXCLUDE_AUTHOR, "OCTOCAT")], &issue));
+}
+
+#[test]
+fn test_matches_filters_title_patterns() {
+    let issue = get_test_issue();
+
+    assert!(matches_filters(&[get_test_issue_filter(KIND_TITLE_PATTERN, r"^\[Bug\]")], &issue));
+    assert!(!matches_filters(&[get_test_issue_filter(KIND_TITLE_PATTERN, r"^\[Feature\]")], &issue));
+}
+
+#[test]
+fn test_validate() {
+    assert!(validate(KIND_INCLUDE_LABEL, "bug").is_ok());
+    assert!(validate("include_milestone", "v1").is_err());
+    assert!(validate(KIND_INCLUDE_LABEL, "").is_err());
+    assert!(validate(KIND_TITLE_PATTERN, "[unclosed").is_err());
+}
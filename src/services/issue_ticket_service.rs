@@ -0,0 +1,117 @@
+use actix::{Actor, Addr, Context, Handler};
+use actix_web::web::Data;
+
+use crate::errors::GITrelloError;
+use crate::models::board_repository::BoardRepository;
+use crate::models::issue_ticket::{IssueTicket, NewIssueTicket};
+use crate::services::repositories::issue_ticket::{
+    CreateIssueTicketMessage, DeleteIssueTicketMessage,
+    GetIssueTicketsByBoardRepositoryIdsAndIssueIdMessage,
+    GetIssueTicketsByBoardRepositoryIdsAndIssueNumberMessage, GetIssueTicketsByTicketIdMessage,
+    IssueTicketRepository, UpdateIssueTicketTicketIdMessage,
+};
+use crate::state::State;
+
+// Generic over the repository actor so tests can keep issue tickets in memory
+pub struct IssueTicketService<A: Actor = IssueTicketRepository> {
+    actor: Addr<A>,
+}
+
+impl IssueTicketService {
+    pub fn new(state: &Data<State>) -> Result<Self, GITrelloError> {
+        let connection = state.get_db_connection()?;
+        let actor = IssueTicketRepository::new(connection).start();
+        Ok(Self { actor })
+    }
+}
+
+impl<A> IssueTicketService<A>
+    where A: Actor<Context = Context<A>>
+        + Handler<CreateIssueTicketMessage>
+        + Handler<GetIssueTicketsByBoardRepositoryIdsAndIssueIdMessage>
+        + Handler<GetIssueTicketsByBoardRepositoryIdsAndIssueNumberMessage>
+        + Handler<GetIssueTicketsByTicketIdMessage>
+        + Handler<UpdateIssueTicketTicketIdMessage>
+        + Handler<DeleteIssueTicketMessage>
+{
+    #[cfg(test)]
+    pub fn with_actor(actor: Addr<A>) -> Self {
+        Self { actor }
+    }
+
+    pub async fn create(
+        &self,
+        board_repository_id: i32,
+        issue_id: i64,
+        issue_number: i32,
+        ticket_id: i64,
+    ) -> Result<IssueTicket, GITrelloError>
+    {
+        self.actor
+            .send(CreateIssueTicketMessage {
+                data: NewIssueTicket { board_repository_id, issue_id, issue_number, ticket_id },
+            })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })?
+    }
+
+    pub async fn get_by_board_repositories_and_issue_id(
+        &self,
+        board_repositories: &[BoardRepository],
+        issue_id: i64,
+    ) -> Result<Vec<IssueTicket>, GITrelloError>
+    {
+        let board_repository_ids = board_repositories
+            .iter()
+            .map(|board_repository| board_repository.id)
+            .collect();
+
+        self.actor
+            .send(GetIssueTicketsByBoardRepositoryIdsAndIssueIdMessage { board_repository_ids, issue_id })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })?
+    }
+
+    pub async fn get_by_board_repositories_and_issue_number(
+        &self,
+        board_repositories: &[BoardRepository],
+        issue_number: i32,
+    ) -> Result<Vec<IssueTicket>, GITrelloError>
+    {
+        let board_repository_ids = board_repositories
+            .iter()
+            .map(|board_repository| board_repository.id)
+            .collect();
+
+        self.actor
+            .send(GetIssueTicketsByBoardRepositoryIdsAndIssueNumberMessage { board_repository_ids, issue_number })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })?
+    }
+
+    pub async fn get_by_ticket_id(&self, ticket_id: i64) -> Result<Vec<IssueTicket>, GITrelloError> {
+        self.actor
+            .send(GetIssueTicketsByTicketIdMessage { ticket_id })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })?
+    }
+
+    pub async fn update_ticket_id(
+        &self,
+        issue_ticket: IssueTicket,
+        ticket_id: i64,
+    ) -> Result<IssueTicket, GITrelloError>
+    {
+        self.actor
+            .send(UpdateIssueTicketTicketIdMessage { issue_ticket, ticket_id })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })?
+    }
+
+    pub async fn delete(&self, id: i32) -> Result<(), GITrelloError> {
+        self.actor
+            .send(DeleteIssueTicketMessage { id })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })?
+    }
+}
@@ -1,6 +1,26 @@
 pub mod board_repository_service;
+pub mod closing_references;
+pub mod column_state_service;
+#[cfg(test)]
+pub mod fakes;
+pub mod github_api_cache;
 pub mod github_api_client;
+pub mod github_issue_service;
 pub mod github_profile_service;
+pub mod github_repository_service;
 pub mod github_webhook_service;
 pub mod gitrello_api_client;
+pub mod import_job_service;
+pub mod import_job_worker;
+pub mod issue_filter_service;
+pub mod issue_ticket_service;
 pub mod repositories;
+pub mod ticket_outbox_service;
+pub mod ticket_outbox_worker;
+pub mod ticket_template;
+pub mod ticket_update_service;
+pub mod webhook_delivery_service;
+pub mod webhook_health_service;
+pub mod webhook_reconciliation_service;
+pub mod webhook_reconciliation_worker;
+pub mod webhook_signature;
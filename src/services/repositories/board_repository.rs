@@ -5,7 +5,7 @@ use diesel::{
 };
 
 use crate::errors::GITrelloError;
-use crate::models::board_repository::{BoardRepository, NewBoardRepository};
+use crate::models::board_repository::{BoardRepository, NewBoardRepository, UpdateBoardRepository};
 use crate::state::DbConnection;
 
 pub struct BoardRepositoryRepository {
@@ -51,38 +51,38 @@ impl BoardRepositoryRepository {
             })
     }
 
-    pub fn get_by_board_id(&self, board_id: i64) -> Result<BoardRepository, GITrelloError> {
-        use crate::schema::board_repository::{table, board_id as board_id_column};
+    pub fn get_by_board_id(&self, board_id: i64) -> Result<Vec<BoardRepository>, GITrelloError> {
+        use crate::schema::board_repository::{table, board_id as board_id_column, id as id_column};
 
         table
             .filter(board_id_column.eq(board_id))
-            .first::<BoardRepository>(&self.connection)
-            .map_err(|source| {
-                match source {
-                    Error::NotFound => GITrelloError::NotFound {
-                        message: String::from(
-                            format!("board_repository for board {} does not exist", board_id),
-                        )
-                    },
-                    _ => GITrelloError::DieselError { source }
-                }
-            })
+            .order(id_column.asc())
+            .load::<BoardRepository>(&self.connection)
+            .map_err(|source| GITrelloError::DieselError { source })
     }
 
-    pub fn get_by_repository_owner_and_name(
+    // Links created before repository ids were stored can only be matched by owner and name. Links that store
+    // an id are matched by it alone, the owner and name may belong to another repository after a rename.
+    pub fn get_by_repository(
         &self,
+        repository_id: i64,
         repository_owner: &str,
         repository_name: &str,
     ) -> Result<Vec<BoardRepository>, GITrelloError>
     {
         use crate::schema::board_repository::{
-            table, repository_owner as repository_owner_column, repository_name as repository_name_column,
+            table, repository_id as repository_id_column, repository_owner as repository_owner_column,
+            repository_name as repository_name_column,
         };
 
         table
             .filter(
-                repository_owner_column.eq(repository_owner)
-                    .and(repository_name_column.eq(repository_name)),
+                repository_id_column.eq(repository_id)
+                    .or(
+                        repository_id_column.is_null()
+                            .and(repository_owner_column.eq(repository_owner))
+                            .and(repository_name_column.eq(repository_name)),
+                    ),
             )
             .load::<BoardRepository>(&self.connection)
             .map_err(|source| GITrelloError::DieselError { source })
@@ -93,17 +93,20 @@ impl BoardRepositoryRepository {
         board_repository: &BoardRepository,
         repository_name: &str,
         repository_owner: &str,
+        repository_id: Option<i64>,
     ) -> Result<BoardRepository, GITrelloError>
     {
         use crate::schema::board_repository::{
             repository_name as repository_name_column,
             repository_owner as repository_owner_column,
+            repository_id as repository_id_column,
         };
 
         update(board_repository)
             .set((
                 repository_name_column.eq(repository_name),
                 repository_owner_column.eq(repository_owner),
+                repository_id_column.eq(repository_id),
             ))
             .get_result::<BoardRepository>(&self.connection)
             .map_err(|source| {
@@ -118,6 +121,25 @@ impl BoardRepositoryRepository {
             })
     }
 
+    pub fn update(
+        &self,
+        board_repository: &BoardRepository,
+        data: &UpdateBoardRepository,
+    ) -> Result<BoardRepository, GITrelloError>
+    {
+        update(board_repository)
+            .set(data)
+            .get_result::<BoardRepository>(&self.connection)
+            .map_err(|source| {
+                match source {
+                    Error::NotFound => GITrelloError::NotFound {
+                        message: format!("board_repository {} does not exist", board_repository.id),
+                    },
+                    _ => GITrelloError::DieselError { source }
+                }
+            })
+    }
+
     pub fn delete(&self, id: i32) -> Result<(), GITrelloError> {
         use crate::schema::board_repository::{table, id as id_column};
 
@@ -169,17 +191,17 @@ impl Handler<GetBoardRepositoryByIdMessage> for BoardRepositoryRepository {
 }
 
 #[derive(Message)]
-#[rtype(result = "Result<BoardRepository, GITrelloError>")]
-pub struct GetBoardRepositoryByBoardIdMessage {
+#[rtype(result = "Result<Vec<BoardRepository>, GITrelloError>")]
+pub struct GetBoardRepositoriesByBoardIdMessage {
     pub board_id: i64,
 }
 
-impl Handler<GetBoardRepositoryByBoardIdMessage> for BoardRepositoryRepository {
-    type Result = Result<BoardRepository, GITrelloError>;
+impl Handler<GetBoardRepositoriesByBoardIdMessage> for BoardRepositoryRepository {
+    type Result = Result<Vec<BoardRepository>, GITrelloError>;
 
     fn handle(
         &mut self,
-        msg: GetBoardRepositoryByBoardIdMessage,
+        msg: GetBoardRepositoriesByBoardIdMessage,
         _ctx: &mut Self::Context,
     ) -> Self::Result
     {
@@ -189,21 +211,26 @@ impl Handler<GetBoardRepositoryByBoardIdMessage> for BoardRepositoryRepository {
 
 #[derive(Message)]
 #[rtype(result = "Result<Vec<BoardRepository>, GITrelloError>")]
-pub struct GetBoardRepositoryByRepositoryOwnerAndNameMessage {
+pub struct GetBoardRepositoriesByRepositoryMessage {
+    pub repository_id: i64,
     pub repository_owner: String,
     pub repository_name: String,
 }
 
-impl Handler<GetBoardRepositoryByRepositoryOwnerAndNameMessage> for BoardRepositoryRepository {
+impl Handler<GetBoardRepositoriesByRepositoryMessage> for BoardRepositoryRepository {
     type Result = Result<Vec<BoardRepository>, GITrelloError>;
 
     fn handle(
         &mut self,
-        msg: GetBoardRepositoryByRepositoryOwnerAndNameMessage,
+        msg: GetBoardRepositoriesByRepositoryMessage,
         _ctx: &mut Self::Context,
     ) -> Self::Result
     {
-        self.get_by_repository_owner_and_name(msg.repository_owner.as_str(), msg.repository_name.as_str())
+        self.get_by_repository(
+            msg.repository_id,
+            msg.repository_owner.as_str(),
+            msg.repository_name.as_str(),
+        )
     }
 }
 
@@ -213,6 +240,7 @@ pub struct UpdateRepositoryDataMessage {
     pub board_repository: BoardRepository,
     pub repository_name: String,
     pub repository_owner: String,
+    pub repository_id: Option<i64>,
 }
 
 impl Handler<UpdateRepositoryDataMessage> for BoardRepositoryRepository {
@@ -228,10 +256,31 @@ impl Handler<UpdateRepositoryDataMessage> for BoardRepositoryRepository {
             &msg.board_repository,
             msg.repository_name.as_str(),
             msg.repository_owner.as_str(),
+            msg.repository_id,
         )
     }
 }
 
+#[derive(Message)]
+#[rtype(result = "Result<BoardRepository, GITrelloError>")]
+pub struct UpdateBoardRepositoryMessage {
+    pub board_repository: BoardRepository,
+    pub data: UpdateBoardRepository,
+}
+
+impl Handler<UpdateBoardRepositoryMessage> for BoardRepositoryRepository {
+    type Result = Result<BoardRepository, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: UpdateBoardRepositoryMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.update(&msg.board_repository, &msg.data)
+    }
+}
+
 #[derive(Message)]
 #[rtype(result = "Result<(), GITrelloError>")]
 pub struct DeleteBoardRepositoryMessage {
@@ -0,0 +1,90 @@
+use actix::{Actor, Context, Handler, Message};
+use diesel::{delete, insert_into, Connection, ExpressionMethods, QueryDsl, RunQueryDsl};
+
+use crate::errors::GITrelloError;
+use crate::models::column_state::{ColumnState, NewColumnState};
+use crate::state::DbConnection;
+
+pub struct ColumnStateRepository {
+    connection: DbConnection,
+}
+
+impl ColumnStateRepository {
+    pub fn new(connection: DbConnection) -> Self {
+        Self { connection }
+    }
+
+    pub fn get_by_board_repository_id(&self, board_repository_id: i32) -> Result<Vec<ColumnState>, GITrelloError> {
+        use crate::schema::column_state::{table, column_id, board_repository_id as board_repository_id_column};
+
+        table
+            .filter(board_repository_id_column.eq(board_repository_id))
+            .order(column_id.asc())
+            .load::<ColumnState>(&self.connection)
+            .map_err(|source| GITrelloError::DieselError { source })
+    }
+
+    // Whole mapping of a board repository is replaced at once
+    pub fn replace(
+        &self,
+        board_repository_id: i32,
+        data: &[NewColumnState],
+    ) -> Result<Vec<ColumnState>, GITrelloError>
+    {
+        use crate::schema::column_state::{table, board_repository_id as board_repository_id_column};
+
+        self.connection
+            .transaction(|| {
+                delete(table.filter(board_repository_id_column.eq(board_repository_id)))
+                    .execute(&self.connection)?;
+
+                insert_into(table)
+                    .values(data)
+                    .get_results::<ColumnState>(&self.connection)
+            })
+            .map_err(|source| GITrelloError::DieselError { source })
+    }
+}
+
+impl Actor for ColumnStateRepository {
+    type Context = Context<Self>;
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<Vec<ColumnState>, GITrelloError>")]
+pub struct GetColumnStatesByBoardRepositoryIdMessage {
+    pub board_repository_id: i32,
+}
+
+impl Handler<GetColumnStatesByBoardRepositoryIdMessage> for ColumnStateRepository {
+    type Result = Result<Vec<ColumnState>, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: GetColumnStatesByBoardRepositoryIdMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.get_by_board_repository_id(msg.board_repository_id)
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<Vec<ColumnState>, GITrelloError>")]
+pub struct ReplaceColumnStatesMessage {
+    pub board_repository_id: i32,
+    pub data: Vec<NewColumnState>,
+}
+
+impl Handler<ReplaceColumnStatesMessage> for ColumnStateRepository {
+    type Result = Result<Vec<ColumnState>, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: ReplaceColumnStatesMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.replace(msg.board_repository_id, &msg.data)
+    }
+}
@@ -33,6 +33,22 @@ impl GithubProfileRepository {
             })
     }
 
+    pub fn get_by_id(&self, id: i32) -> Result<GithubProfile, GITrelloError> {
+        use crate::schema::github_profile::{table, id as id_column};
+
+        table
+            .filter(id_column.eq(id))
+            .first::<GithubProfile>(&self.connection)
+            .map_err(|source| {
+                match source {
+                    Error::NotFound => GITrelloError::NotFound {
+                        message: format!("github_profile {} does not exist", id),
+                    },
+                    _ => GITrelloError::DieselError { source }
+                }
+            })
+    }
+
     pub fn get_by_user_id(&self, user_id: i64) -> Result<GithubProfile, GITrelloError> {
         use crate::schema::github_profile::{table, user_id as user_id_column};
 
@@ -100,6 +116,25 @@ impl Handler<CreateGithubProfileMessage> for GithubProfileRepository {
     }
 }
 
+#[derive(Message)]
+#[rtype(result = "Result<GithubProfile, GITrelloError>")]
+pub struct GetGithubProfileByIdMessage {
+    pub id: i32,
+}
+
+impl Handler<GetGithubProfileByIdMessage> for GithubProfileRepository {
+    type Result = Result<GithubProfile, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: GetGithubProfileByIdMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.get_by_id(msg.id)
+    }
+}
+
 #[derive(Message)]
 #[rtype(result = "Result<GithubProfile, GITrelloError>")]
 pub struct GetGithubProfileByUserIdMessage {
@@ -2,7 +2,10 @@ use actix::{Actor, Context, Handler, Message};
 use diesel::{insert_into, update, result::Error, BoolExpressionMethods, ExpressionMethods, QueryDsl, RunQueryDsl};
 
 use crate::errors::GITrelloError;
-use crate::models::github_webhook::{GithubWebhook, GithubWebhookWithRepositoryInfo, NewGithubWebhook};
+use crate::models::board_repository::BoardRepository;
+use crate::models::github_webhook::{
+    GithubWebhook, GithubWebhookWithRepositoryInfo, NewGithubWebhook, UpdateGithubWebhookHealth,
+};
 use crate::state::DbConnection;
 
 pub struct GithubWebhookRepository {
@@ -28,12 +31,16 @@ impl GithubWebhookRepository {
         &self,
         github_webhook: &GithubWebhook,
         webhook_id: i64,
+        url: &str,
+        secret: &str,
     ) -> Result<GithubWebhook, GITrelloError>
     {
-        use crate::schema::github_webhook::{webhook_id as webhook_id_column};
+        use crate::schema::github_webhook::{
+            webhook_id as webhook_id_column, url as url_column, secret as secret_column,
+        };
 
         update(github_webhook)
-            .set(webhook_id_column.eq(webhook_id))
+            .set((webhook_id_column.eq(webhook_id), url_column.eq(url), secret_column.eq(secret)))
             .get_result::<GithubWebhook>(&self.connection)
             .map_err(|source| {
                 match source {
@@ -47,6 +54,56 @@ impl GithubWebhookRepository {
             })
     }
 
+    // The same GitHub hook may be shared by several board repositories, all of them are updated
+    pub fn update_health(
+        &self,
+        webhook_id: i64,
+        data: &UpdateGithubWebhookHealth,
+    ) -> Result<(), GITrelloError>
+    {
+        use crate::schema::github_webhook::{table, webhook_id as webhook_id_column};
+
+        update(table.filter(webhook_id_column.eq(webhook_id)))
+            .set(data)
+            .execute(&self.connection)?;
+        Ok(())
+    }
+
+    pub fn get_by_board_repository_id(&self, board_repository_id: i32) -> Result<GithubWebhook, GITrelloError> {
+        use crate::schema::github_webhook::{table, board_repository_id as board_repository_id_column};
+
+        table
+            .filter(board_repository_id_column.eq(board_repository_id))
+            .first::<GithubWebhook>(&self.connection)
+            .map_err(|source| {
+                match source {
+                    Error::NotFound => GITrelloError::NotFound {
+                        message: format!(
+                            "github_webhook for board_repository {} does not exist",
+                            board_repository_id,
+                        ),
+                    },
+                    _ => GITrelloError::DieselError { source }
+                }
+            })
+    }
+
+    pub fn get_by_webhook_id(&self, webhook_id: i64) -> Result<GithubWebhook, GITrelloError> {
+        use crate::schema::github_webhook::{table, webhook_id as webhook_id_column};
+
+        table
+            .filter(webhook_id_column.eq(webhook_id))
+            .first::<GithubWebhook>(&self.connection)
+            .map_err(|source| {
+                match source {
+                    Error::NotFound => GITrelloError::NotFound {
+                        message: format!("github_webhook with webhook_id {} does not exist", webhook_id),
+                    },
+                    _ => GITrelloError::DieselError { source }
+                }
+            })
+    }
+
     pub fn get_by_repository_name_and_owner(
         &self,
         repository_name: &str,
@@ -80,6 +137,35 @@ impl GithubWebhookRepository {
             })
     }
 
+    pub fn get_all_with_board_repository(&self) -> Result<Vec<(GithubWebhook, BoardRepository)>, GITrelloError> {
+        use crate::schema::github_webhook;
+        use crate::schema::board_repository;
+
+        github_webhook::table
+            .inner_join(board_repository::table)
+            .select((github_webhook::all_columns, board_repository::all_columns))
+            .order(github_webhook::id.asc())
+            .load::<(GithubWebhook, BoardRepository)>(&self.connection)
+            .map_err(|source| GITrelloError::DieselError { source })
+    }
+
+    // Board repositories sharing the same GitHub hook
+    pub fn get_board_repositories_by_webhook_id(
+        &self,
+        webhook_id: i64,
+    ) -> Result<Vec<BoardRepository>, GITrelloError>
+    {
+        use crate::schema::github_webhook;
+        use crate::schema::board_repository;
+
+        github_webhook::table
+            .inner_join(board_repository::table)
+            .select(board_repository::all_columns)
+            .filter(github_webhook::webhook_id.eq(webhook_id))
+            .load::<BoardRepository>(&self.connection)
+            .map_err(|source| GITrelloError::DieselError { source })
+    }
+
     pub fn get_distinct_webhooks_by_github_profile_id(
         &self,
         github_profile_id: i32,
@@ -134,6 +220,8 @@ impl Handler<CreateGithubWebhookMessage> for GithubWebhookRepository {
 pub struct UpdateWebhookIdMessage {
     pub github_webhook: GithubWebhook,
     pub webhook_id: i64,
+    pub url: String,
+    pub secret: String,
 }
 
 impl Handler<UpdateWebhookIdMessage> for GithubWebhookRepository {
@@ -145,7 +233,65 @@ impl Handler<UpdateWebhookIdMessage> for GithubWebhookRepository {
         _ctx: &mut Self::Context,
     ) -> Self::Result
     {
-        self.update_webhook_id(&msg.github_webhook, msg.webhook_id)
+        self.update_webhook_id(&msg.github_webhook, msg.webhook_id, msg.url.as_str(), msg.secret.as_str())
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<(), GITrelloError>")]
+pub struct UpdateHealthMessage {
+    pub webhook_id: i64,
+    pub data: UpdateGithubWebhookHealth,
+}
+
+impl Handler<UpdateHealthMessage> for GithubWebhookRepository {
+    type Result = Result<(), GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: UpdateHealthMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.update_health(msg.webhook_id, &msg.data)
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<GithubWebhook, GITrelloError>")]
+pub struct GetByBoardRepositoryIdMessage {
+    pub board_repository_id: i32,
+}
+
+impl Handler<GetByBoardRepositoryIdMessage> for GithubWebhookRepository {
+    type Result = Result<GithubWebhook, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: GetByBoardRepositoryIdMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.get_by_board_repository_id(msg.board_repository_id)
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<GithubWebhook, GITrelloError>")]
+pub struct GetByWebhookIdMessage {
+    pub webhook_id: i64,
+}
+
+impl Handler<GetByWebhookIdMessage> for GithubWebhookRepository {
+    type Result = Result<GithubWebhook, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: GetByWebhookIdMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.get_by_webhook_id(msg.webhook_id)
     }
 }
 
@@ -190,3 +336,39 @@ impl Handler<GetDistinctWebhooksByGithubProfileIdMessage> for GithubWebhookRepos
         self.get_distinct_webhooks_by_github_profile_id(msg.github_profile_id)
     }
 }
+
+#[derive(Message)]
+#[rtype(result = "Result<Vec<(GithubWebhook, BoardRepository)>, GITrelloError>")]
+pub struct GetAllGithubWebhooksWithBoardRepositoryMessage;
+
+impl Handler<GetAllGithubWebhooksWithBoardRepositoryMessage> for GithubWebhookRepository {
+    type Result = Result<Vec<(GithubWebhook, BoardRepository)>, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        _msg: GetAllGithubWebhooksWithBoardRepositoryMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.get_all_with_board_repository()
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<Vec<BoardRepository>, GITrelloError>")]
+pub struct GetBoardRepositoriesByWebhookIdMessage {
+    pub webhook_id: i64,
+}
+
+impl Handler<GetBoardRepositoriesByWebhookIdMessage> for GithubWebhookRepository {
+    type Result = Result<Vec<BoardRepository>, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: GetBoardRepositoriesByWebhookIdMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.get_board_repositories_by_webhook_id(msg.webhook_id)
+    }
+}
@@ -0,0 +1,222 @@
+use actix::{Actor, Context, Handler, Message};
+use chrono::{DateTime, Utc};
+use diesel::{insert_into, update, result::Error, BoolExpressionMethods, ExpressionMethods, QueryDsl, RunQueryDsl};
+
+use crate::errors::GITrelloError;
+use crate::models::import_job::{ImportJob, NewImportJob, UpdateImportJob};
+use crate::state::DbConnection;
+
+pub struct ImportJobRepository {
+    connection: DbConnection,
+}
+
+impl ImportJobRepository {
+    pub fn new(connection: DbConnection) -> Self {
+        Self { connection }
+    }
+
+    pub fn create(&self, data: &NewImportJob) -> Result<ImportJob, GITrelloError> {
+        use crate::schema::import_job::dsl::*;
+
+        insert_into(import_job)
+            .values(data)
+            .get_result(&self.connection)
+            .map_err(|source| GITrelloError::DieselError { source })
+    }
+
+    pub fn get_by_id(&self, id: i32) -> Result<ImportJob, GITrelloError> {
+        use crate::schema::import_job::{table, id as id_column};
+
+        table
+            .filter(id_column.eq(id))
+            .first::<ImportJob>(&self.connection)
+            .map_err(|source| {
+                match source {
+                    Error::NotFound => GITrelloError::NotFound {
+                        message: format!("import_job {} does not exist", id),
+                    },
+                    _ => GITrelloError::DieselError { source }
+                }
+            })
+    }
+
+    pub fn get_latest_by_board_repository_id(
+        &self,
+        board_repository_id: i32,
+    ) -> Result<Option<ImportJob>, GITrelloError>
+    {
+        use crate::schema::import_job::{table, id, board_repository_id as board_repository_id_column};
+
+        let import_jobs = table
+            .filter(board_repository_id_column.eq(board_repository_id))
+            .order(id.desc())
+            .limit(1)
+            .load::<ImportJob>(&self.connection)
+            .map_err(|source| GITrelloError::DieselError { source })?;
+
+        Ok(import_jobs.into_iter().next())
+    }
+
+    pub fn get_next_by_status(&self, status: &str) -> Result<Option<ImportJob>, GITrelloError> {
+        use crate::schema::import_job::{table, created_at, status as status_column};
+
+        let import_jobs = table
+            .filter(status_column.eq(status))
+            .order(created_at.asc())
+            .limit(1)
+            .load::<ImportJob>(&self.connection)
+            .map_err(|source| GITrelloError::DieselError { source })?;
+
+        Ok(import_jobs.into_iter().next())
+    }
+
+    pub fn reset_stale(
+        &self,
+        status: &str,
+        updated_before: DateTime<Utc>,
+        new_status: &str,
+    ) -> Result<usize, GITrelloError>
+    {
+        use crate::schema::import_job::{table, status as status_column, updated_at};
+
+        update(table.filter(status_column.eq(status).and(updated_at.lt(updated_before))))
+            .set((status_column.eq(new_status), updated_at.eq(Utc::now())))
+            .execute(&self.connection)
+            .map_err(|source| GITrelloError::DieselError { source })
+    }
+
+    pub fn update(&self, import_job: &ImportJob, data: &UpdateImportJob) -> Result<ImportJob, GITrelloError> {
+        update(import_job)
+            .set(data)
+            .get_result::<ImportJob>(&self.connection)
+            .map_err(|source| {
+                match source {
+                    Error::NotFound => GITrelloError::NotFound {
+                        message: format!("import_job {} does not exist", import_job.id),
+                    },
+                    _ => GITrelloError::DieselError { source }
+                }
+            })
+    }
+}
+
+impl Actor for ImportJobRepository {
+    type Context = Context<Self>;
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<ImportJob, GITrelloError>")]
+pub struct CreateImportJobMessage {
+    pub data: NewImportJob,
+}
+
+impl Handler<CreateImportJobMessage> for ImportJobRepository {
+    type Result = Result<ImportJob, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: CreateImportJobMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.create(&msg.data)
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<ImportJob, GITrelloError>")]
+pub struct GetImportJobByIdMessage {
+    pub id: i32,
+}
+
+impl Handler<GetImportJobByIdMessage> for ImportJobRepository {
+    type Result = Result<ImportJob, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: GetImportJobByIdMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.get_by_id(msg.id)
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<Option<ImportJob>, GITrelloError>")]
+pub struct GetLatestImportJobByBoardRepositoryIdMessage {
+    pub board_repository_id: i32,
+}
+
+impl Handler<GetLatestImportJobByBoardRepositoryIdMessage> for ImportJobRepository {
+    type Result = Result<Option<ImportJob>, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: GetLatestImportJobByBoardRepositoryIdMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.get_latest_by_board_repository_id(msg.board_repository_id)
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<Option<ImportJob>, GITrelloError>")]
+pub struct GetNextImportJobByStatusMessage {
+    pub status: String,
+}
+
+impl Handler<GetNextImportJobByStatusMessage> for ImportJobRepository {
+    type Result = Result<Option<ImportJob>, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: GetNextImportJobByStatusMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.get_next_by_status(msg.status.as_str())
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<usize, GITrelloError>")]
+pub struct ResetStaleImportJobsMessage {
+    pub status: String,
+    pub updated_before: DateTime<Utc>,
+    pub new_status: String,
+}
+
+impl Handler<ResetStaleImportJobsMessage> for ImportJobRepository {
+    type Result = Result<usize, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: ResetStaleImportJobsMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.reset_stale(msg.status.as_str(), msg.updated_before, msg.new_status.as_str())
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<ImportJob, GITrelloError>")]
+pub struct UpdateImportJobMessage {
+    pub import_job: ImportJob,
+    pub data: UpdateImportJob,
+}
+
+impl Handler<UpdateImportJobMessage> for ImportJobRepository {
+    type Result = Result<ImportJob, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: UpdateImportJobMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.update(&msg.import_job, &msg.data)
+    }
+}
@@ -0,0 +1,185 @@
+use actix::{Actor, Context, Handler, Message};
+use diesel::{delete, insert_into, update, result::Error, ExpressionMethods, QueryDsl, RunQueryDsl};
+
+use crate::errors::GITrelloError;
+use crate::models::issue_filter::{IssueFilter, NewIssueFilter};
+use crate::state::DbConnection;
+
+pub struct IssueFilterRepository {
+    connection: DbConnection,
+}
+
+impl IssueFilterRepository {
+    pub fn new(connection: DbConnection) -> Self {
+        Self { connection }
+    }
+
+    pub fn create(&self, data: &NewIssueFilter) -> Result<IssueFilter, GITrelloError> {
+        use crate::schema::issue_filter::dsl::*;
+
+        insert_into(issue_filter)
+            .values(data)
+            .get_result(&self.connection)
+            .map_err(|source| GITrelloError::DieselError { source })
+    }
+
+    pub fn get_by_id(&self, id: i32) -> Result<IssueFilter, GITrelloError> {
+        use crate::schema::issue_filter::{table, id as id_column};
+
+        table
+            .filter(id_column.eq(id))
+            .first::<IssueFilter>(&self.connection)
+            .map_err(|source| {
+                match source {
+                    Error::NotFound => GITrelloError::NotFound {
+                        message: format!("issue_filter {} does not exist", id),
+                    },
+                    _ => GITrelloError::DieselError { source }
+                }
+            })
+    }
+
+    pub fn get_by_board_repository_ids(
+        &self,
+        board_repository_ids: &[i32],
+    ) -> Result<Vec<IssueFilter>, GITrelloError>
+    {
+        use crate::schema::issue_filter::{table, id, board_repository_id};
+
+        table
+            .filter(board_repository_id.eq_any(board_repository_ids))
+            .order(id.asc())
+            .load::<IssueFilter>(&self.connection)
+            .map_err(|source| GITrelloError::DieselError { source })
+    }
+
+    pub fn update(
+        &self,
+        issue_filter: &IssueFilter,
+        kind: &str,
+        value: &str,
+    ) -> Result<IssueFilter, GITrelloError>
+    {
+        use crate::schema::issue_filter::{kind as kind_column, value as value_column};
+
+        update(issue_filter)
+            .set((kind_column.eq(kind), value_column.eq(value)))
+            .get_result::<IssueFilter>(&self.connection)
+            .map_err(|source| {
+                match source {
+                    Error::NotFound => GITrelloError::NotFound {
+                        message: format!("issue_filter {} does not exist", issue_filter.id),
+                    },
+                    _ => GITrelloError::DieselError { source }
+                }
+            })
+    }
+
+    pub fn delete(&self, id: i32) -> Result<(), GITrelloError> {
+        use crate::schema::issue_filter::{table, id as id_column};
+
+        delete(table.filter(id_column.eq(id))).execute(&self.connection)?;
+        Ok(())
+    }
+}
+
+impl Actor for IssueFilterRepository {
+    type Context = Context<Self>;
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<IssueFilter, GITrelloError>")]
+pub struct CreateIssueFilterMessage {
+    pub data: NewIssueFilter,
+}
+
+impl Handler<CreateIssueFilterMessage> for IssueFilterRepository {
+    type Result = Result<IssueFilter, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: CreateIssueFilterMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.create(&msg.data)
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<IssueFilter, GITrelloError>")]
+pub struct GetIssueFilterByIdMessage {
+    pub id: i32,
+}
+
+impl Handler<GetIssueFilterByIdMessage> for IssueFilterRepository {
+    type Result = Result<IssueFilter, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: GetIssueFilterByIdMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.get_by_id(msg.id)
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<Vec<IssueFilter>, GITrelloError>")]
+pub struct GetIssueFiltersByBoardRepositoryIdsMessage {
+    pub board_repository_ids: Vec<i32>,
+}
+
+impl Handler<GetIssueFiltersByBoardRepositoryIdsMessage> for IssueFilterRepository {
+    type Result = Result<Vec<IssueFilter>, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: GetIssueFiltersByBoardRepositoryIdsMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.get_by_board_repository_ids(&msg.board_repository_ids)
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<IssueFilter, GITrelloError>")]
+pub struct UpdateIssueFilterMessage {
+    pub issue_filter: IssueFilter,
+    pub kind: String,
+    pub value: String,
+}
+
+impl Handler<UpdateIssueFilterMessage> for IssueFilterRepository {
+    type Result = Result<IssueFilter, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: UpdateIssueFilterMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.update(&msg.issue_filter, msg.kind.as_str(), msg.value.as_str())
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<(), GITrelloError>")]
+pub struct DeleteIssueFilterMessage {
+    pub id: i32,
+}
+
+impl Handler<DeleteIssueFilterMessage> for IssueFilterRepository {
+    type Result = Result<(), GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: DeleteIssueFilterMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.delete(msg.id)
+    }
+}
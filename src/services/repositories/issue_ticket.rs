@@ -0,0 +1,227 @@
+use actix::{Actor, Context, Handler, Message};
+use diesel::{
+    delete, insert_into, result::DatabaseErrorKind, result::Error, update, BoolExpressionMethods,
+    ExpressionMethods, QueryDsl, RunQueryDsl,
+};
+
+use crate::errors::GITrelloError;
+use crate::models::issue_ticket::{IssueTicket, NewIssueTicket};
+use crate::state::DbConnection;
+
+pub struct IssueTicketRepository {
+    connection: DbConnection,
+}
+
+impl IssueTicketRepository {
+    pub fn new(connection: DbConnection) -> Self {
+        Self { connection }
+    }
+
+    pub fn create(&self, data: &NewIssueTicket) -> Result<IssueTicket, GITrelloError> {
+        use crate::schema::issue_ticket::dsl::*;
+
+        insert_into(issue_ticket)
+            .values(data)
+            .get_result(&self.connection)
+            .map_err(|source| {
+                match source {
+                    Error::DatabaseError(DatabaseErrorKind::UniqueViolation, error_info) => {
+                        GITrelloError::AlreadyExists { message: String::from(error_info.message()) }
+                    },
+                    _ => GITrelloError::DieselError { source }
+                }
+            })
+    }
+
+    pub fn get_by_board_repository_ids_and_issue_id(
+        &self,
+        board_repository_ids: &[i32],
+        issue_id: i64,
+    ) -> Result<Vec<IssueTicket>, GITrelloError>
+    {
+        use crate::schema::issue_ticket::{
+            table, board_repository_id as board_repository_id_column, issue_id as issue_id_column,
+        };
+
+        table
+            .filter(
+                board_repository_id_column.eq_any(board_repository_ids)
+                    .and(issue_id_column.eq(issue_id)),
+            )
+            .load::<IssueTicket>(&self.connection)
+            .map_err(|source| GITrelloError::DieselError { source })
+    }
+
+    pub fn get_by_board_repository_ids_and_issue_number(
+        &self,
+        board_repository_ids: &[i32],
+        issue_number: i32,
+    ) -> Result<Vec<IssueTicket>, GITrelloError>
+    {
+        use crate::schema::issue_ticket::{
+            table, board_repository_id as board_repository_id_column,
+            issue_number as issue_number_column,
+        };
+
+        table
+            .filter(
+                board_repository_id_column.eq_any(board_repository_ids)
+                    .and(issue_number_column.eq(issue_number)),
+            )
+            .load::<IssueTicket>(&self.connection)
+            .map_err(|source| GITrelloError::DieselError { source })
+    }
+
+    pub fn get_by_ticket_id(&self, ticket_id: i64) -> Result<Vec<IssueTicket>, GITrelloError> {
+        use crate::schema::issue_ticket::{table, ticket_id as ticket_id_column};
+
+        table
+            .filter(ticket_id_column.eq(ticket_id))
+            .load::<IssueTicket>(&self.connection)
+            .map_err(|source| GITrelloError::DieselError { source })
+    }
+
+    pub fn update_ticket_id(&self, issue_ticket: &IssueTicket, ticket_id: i64) -> Result<IssueTicket, GITrelloError> {
+        use crate::schema::issue_ticket::ticket_id as ticket_id_column;
+
+        update(issue_ticket)
+            .set(ticket_id_column.eq(ticket_id))
+            .get_result::<IssueTicket>(&self.connection)
+            .map_err(|source| {
+                match source {
+                    Error::NotFound => GITrelloError::NotFound {
+                        message: format!("issue_ticket {} does not exist", issue_ticket.id),
+                    },
+                    _ => GITrelloError::DieselError { source }
+                }
+            })
+    }
+
+    pub fn delete(&self, id: i32) -> Result<(), GITrelloError> {
+        use crate::schema::issue_ticket::{table, id as id_column};
+
+        delete(table.filter(id_column.eq(id))).execute(&self.connection)?;
+        Ok(())
+    }
+}
+
+impl Actor for IssueTicketRepository {
+    type Context = Context<Self>;
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<IssueTicket, GITrelloError>")]
+pub struct CreateIssueTicketMessage {
+    pub data: NewIssueTicket,
+}
+
+impl Handler<CreateIssueTicketMessage> for IssueTicketRepository {
+    type Result = Result<IssueTicket, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: CreateIssueTicketMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.create(&msg.data)
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<Vec<IssueTicket>, GITrelloError>")]
+pub struct GetIssueTicketsByBoardRepositoryIdsAndIssueIdMessage {
+    pub board_repository_ids: Vec<i32>,
+    pub issue_id: i64,
+}
+
+impl Handler<GetIssueTicketsByBoardRepositoryIdsAndIssueIdMessage> for IssueTicketRepository {
+    type Result = Result<Vec<IssueTicket>, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: GetIssueTicketsByBoardRepositoryIdsAndIssueIdMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.get_by_board_repository_ids_and_issue_id(&msg.board_repository_ids, msg.issue_id)
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<Vec<IssueTicket>, GITrelloError>")]
+pub struct GetIssueTicketsByBoardRepositoryIdsAndIssueNumberMessage {
+    pub board_repository_ids: Vec<i32>,
+    pub issue_number: i32,
+}
+
+impl Handler<GetIssueTicketsByBoardRepositoryIdsAndIssueNumberMessage> for IssueTicketRepository {
+    type Result = Result<Vec<IssueTicket>, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: GetIssueTicketsByBoardRepositoryIdsAndIssueNumberMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.get_by_board_repository_ids_and_issue_number(&msg.board_repository_ids, msg.issue_number)
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<Vec<IssueTicket>, GITrelloError>")]
+pub struct GetIssueTicketsByTicketIdMessage {
+    pub ticket_id: i64,
+}
+
+impl Handler<GetIssueTicketsByTicketIdMessage> for IssueTicketRepository {
+    type Result = Result<Vec<IssueTicket>, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: GetIssueTicketsByTicketIdMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.get_by_ticket_id(msg.ticket_id)
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<IssueTicket, GITrelloError>")]
+pub struct UpdateIssueTicketTicketIdMessage {
+    pub issue_ticket: IssueTicket,
+    pub ticket_id: i64,
+}
+
+impl Handler<UpdateIssueTicketTicketIdMessage> for IssueTicketRepository {
+    type Result = Result<IssueTicket, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: UpdateIssueTicketTicketIdMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.update_ticket_id(&msg.issue_ticket, msg.ticket_id)
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<(), GITrelloError>")]
+pub struct DeleteIssueTicketMessage {
+    pub id: i32,
+}
+
+impl Handler<DeleteIssueTicketMessage> for IssueTicketRepository {
+    type Result = Result<(), GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: DeleteIssueTicketMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.delete(msg.id)
+    }
+}
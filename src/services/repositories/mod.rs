@@ -1,3 +1,10 @@
 pub mod github_profile;
 pub mod board_repository;
+pub mod column_state;
 pub mod github_webhook;
+pub mod webhook_delivery;
+pub mod ticket_outbox;
+pub mod issue_ticket;
+pub mod ticket_comment;
+pub mod import_job;
+pub mod issue_filter;
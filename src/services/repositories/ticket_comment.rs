@@ -0,0 +1,123 @@
+use actix::{Actor, Context, Handler, Message};
+use diesel::{
+    delete, insert_into, result::DatabaseErrorKind, result::Error, BoolExpressionMethods,
+    ExpressionMethods, QueryDsl, RunQueryDsl,
+};
+
+use crate::errors::GITrelloError;
+use crate::models::ticket_comment::{NewTicketComment, TicketComment};
+use crate::state::DbConnection;
+
+pub struct TicketCommentRepository {
+    connection: DbConnection,
+}
+
+impl TicketCommentRepository {
+    pub fn new(connection: DbConnection) -> Self {
+        Self { connection }
+    }
+
+    pub fn create(&self, data: &NewTicketComment) -> Result<TicketComment, GITrelloError> {
+        use crate::schema::ticket_comment::dsl::*;
+
+        insert_into(ticket_comment)
+            .values(data)
+            .get_result(&self.connection)
+            .map_err(|source| {
+                match source {
+                    Error::DatabaseError(DatabaseErrorKind::UniqueViolation, error_info) => {
+                        GITrelloError::AlreadyExists { message: String::from(error_info.message()) }
+                    },
+                    _ => GITrelloError::DieselError { source }
+                }
+            })
+    }
+
+    pub fn get_by_issue_ticket_ids_and_comment_id(
+        &self,
+        issue_ticket_ids: &[i32],
+        comment_id: i64,
+    ) -> Result<Vec<TicketComment>, GITrelloError>
+    {
+        use crate::schema::ticket_comment::{
+            table, issue_ticket_id as issue_ticket_id_column, comment_id as comment_id_column,
+        };
+
+        table
+            .filter(
+                issue_ticket_id_column.eq_any(issue_ticket_ids)
+                    .and(comment_id_column.eq(comment_id)),
+            )
+            .load::<TicketComment>(&self.connection)
+            .map_err(|source| GITrelloError::DieselError { source })
+    }
+
+    pub fn delete(&self, id: i32) -> Result<(), GITrelloError> {
+        use crate::schema::ticket_comment::{table, id as id_column};
+
+        delete(table.filter(id_column.eq(id))).execute(&self.connection)?;
+        Ok(())
+    }
+}
+
+impl Actor for TicketCommentRepository {
+    type Context = Context<Self>;
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<TicketComment, GITrelloError>")]
+pub struct CreateTicketCommentMessage {
+    pub data: NewTicketComment,
+}
+
+impl Handler<CreateTicketCommentMessage> for TicketCommentRepository {
+    type Result = Result<TicketComment, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: CreateTicketCommentMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.create(&msg.data)
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<Vec<TicketComment>, GITrelloError>")]
+pub struct GetTicketCommentsByIssueTicketIdsAndCommentIdMessage {
+    pub issue_ticket_ids: Vec<i32>,
+    pub comment_id: i64,
+}
+
+impl Handler<GetTicketCommentsByIssueTicketIdsAndCommentIdMessage> for TicketCommentRepository {
+    type Result = Result<Vec<TicketComment>, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: GetTicketCommentsByIssueTicketIdsAndCommentIdMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.get_by_issue_ticket_ids_and_comment_id(&msg.issue_ticket_ids, msg.comment_id)
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<(), GITrelloError>")]
+pub struct DeleteTicketCommentMessage {
+    pub id: i32,
+}
+
+impl Handler<DeleteTicketCommentMessage> for TicketCommentRepository {
+    type Result = Result<(), GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: DeleteTicketCommentMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.delete(msg.id)
+    }
+}
@@ -0,0 +1,236 @@
+use actix::{Actor, Context, Handler, Message};
+use chrono::{DateTime, Utc};
+use diesel::{
+    delete, insert_into, update, result::Error, BoolExpressionMethods, ExpressionMethods,
+    QueryDsl, RunQueryDsl,
+};
+
+use crate::errors::GITrelloError;
+use crate::models::ticket_outbox::{NewTicketOutboxItem, TicketOutboxItem, STATUS_PENDING};
+use crate::state::DbConnection;
+
+pub struct TicketOutboxRepository {
+    connection: DbConnection,
+}
+
+impl TicketOutboxRepository {
+    pub fn new(connection: DbConnection) -> Self {
+        Self { connection }
+    }
+
+    pub fn create(&self, data: &NewTicketOutboxItem) -> Result<TicketOutboxItem, GITrelloError> {
+        use crate::schema::ticket_outbox::dsl::*;
+
+        insert_into(ticket_outbox)
+            .values(data)
+            .get_result(&self.connection)
+            .map_err(|source| GITrelloError::DieselError { source })
+    }
+
+    pub fn get_by_id(&self, id: i32) -> Result<TicketOutboxItem, GITrelloError> {
+        use crate::schema::ticket_outbox::{table, id as id_column};
+
+        table
+            .filter(id_column.eq(id))
+            .first::<TicketOutboxItem>(&self.connection)
+            .map_err(|source| {
+                match source {
+                    Error::NotFound => GITrelloError::NotFound {
+                        message: format!("ticket_outbox item {} does not exist", id),
+                    },
+                    _ => GITrelloError::DieselError { source }
+                }
+            })
+    }
+
+    pub fn get_due(&self, now: DateTime<Utc>, limit: i64) -> Result<Vec<TicketOutboxItem>, GITrelloError> {
+        use crate::schema::ticket_outbox::{table, status, next_attempt_at};
+
+        table
+            .filter(status.eq(STATUS_PENDING).and(next_attempt_at.le(now)))
+            .order(next_attempt_at.asc())
+            .limit(limit)
+            .load::<TicketOutboxItem>(&self.connection)
+            .map_err(|source| GITrelloError::DieselError { source })
+    }
+
+    pub fn get_by_status(&self, status: &str, limit: i64) -> Result<Vec<TicketOutboxItem>, GITrelloError> {
+        use crate::schema::ticket_outbox::{table, status as status_column, created_at};
+
+        table
+            .filter(status_column.eq(status))
+            .order(created_at.desc())
+            .limit(limit)
+            .load::<TicketOutboxItem>(&self.connection)
+            .map_err(|source| GITrelloError::DieselError { source })
+    }
+
+    pub fn update_attempt(
+        &self,
+        ticket_outbox_item: &TicketOutboxItem,
+        status: &str,
+        attempts: i32,
+        next_attempt_at: DateTime<Utc>,
+        last_error: Option<&str>,
+    ) -> Result<TicketOutboxItem, GITrelloError>
+    {
+        use crate::schema::ticket_outbox::{
+            status as status_column, attempts as attempts_column,
+            next_attempt_at as next_attempt_at_column, last_error as last_error_column,
+        };
+
+        update(ticket_outbox_item)
+            .set((
+                status_column.eq(status),
+                attempts_column.eq(attempts),
+                next_attempt_at_column.eq(next_attempt_at),
+                last_error_column.eq(last_error),
+            ))
+            .get_result::<TicketOutboxItem>(&self.connection)
+            .map_err(|source| {
+                match source {
+                    Error::NotFound => GITrelloError::NotFound {
+                        message: format!("ticket_outbox item {} does not exist", ticket_outbox_item.id),
+                    },
+                    _ => GITrelloError::DieselError { source }
+                }
+            })
+    }
+
+    pub fn delete(&self, id: i32) -> Result<(), GITrelloError> {
+        use crate::schema::ticket_outbox::{table, id as id_column};
+
+        delete(table.filter(id_column.eq(id))).execute(&self.connection)?;
+        Ok(())
+    }
+}
+
+impl Actor for TicketOutboxRepository {
+    type Context = Context<Self>;
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<TicketOutboxItem, GITrelloError>")]
+pub struct CreateTicketOutboxItemMessage {
+    pub data: NewTicketOutboxItem,
+}
+
+impl Handler<CreateTicketOutboxItemMessage> for TicketOutboxRepository {
+    type Result = Result<TicketOutboxItem, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: CreateTicketOutboxItemMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.create(&msg.data)
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<TicketOutboxItem, GITrelloError>")]
+pub struct GetTicketOutboxItemByIdMessage {
+    pub id: i32,
+}
+
+impl Handler<GetTicketOutboxItemByIdMessage> for TicketOutboxRepository {
+    type Result = Result<TicketOutboxItem, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: GetTicketOutboxItemByIdMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.get_by_id(msg.id)
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<Vec<TicketOutboxItem>, GITrelloError>")]
+pub struct GetDueTicketOutboxItemsMessage {
+    pub now: DateTime<Utc>,
+    pub limit: i64,
+}
+
+impl Handler<GetDueTicketOutboxItemsMessage> for TicketOutboxRepository {
+    type Result = Result<Vec<TicketOutboxItem>, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: GetDueTicketOutboxItemsMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.get_due(msg.now, msg.limit)
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<Vec<TicketOutboxItem>, GITrelloError>")]
+pub struct GetTicketOutboxItemsByStatusMessage {
+    pub status: String,
+    pub limit: i64,
+}
+
+impl Handler<GetTicketOutboxItemsByStatusMessage> for TicketOutboxRepository {
+    type Result = Result<Vec<TicketOutboxItem>, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: GetTicketOutboxItemsByStatusMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.get_by_status(msg.status.as_str(), msg.limit)
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<TicketOutboxItem, GITrelloError>")]
+pub struct UpdateTicketOutboxItemAttemptMessage {
+    pub ticket_outbox_item: TicketOutboxItem,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+}
+
+impl Handler<UpdateTicketOutboxItemAttemptMessage> for TicketOutboxRepository {
+    type Result = Result<TicketOutboxItem, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: UpdateTicketOutboxItemAttemptMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.update_attempt(
+            &msg.ticket_outbox_item,
+            msg.status.as_str(),
+            msg.attempts,
+            msg.next_attempt_at,
+            msg.last_error.as_deref(),
+        )
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<(), GITrelloError>")]
+pub struct DeleteTicketOutboxItemMessage {
+    pub id: i32,
+}
+
+impl Handler<DeleteTicketOutboxItemMessage> for TicketOutboxRepository {
+    type Result = Result<(), GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: DeleteTicketOutboxItemMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.delete(msg.id)
+    }
+}
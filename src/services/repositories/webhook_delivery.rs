@@ -0,0 +1,276 @@
+use actix::{Actor, Context, Handler, Message};
+use chrono::{DateTime, Utc};
+use diesel::{
+    insert_into, update, result::DatabaseErrorKind, result::Error, BoolExpressionMethods,
+    ExpressionMethods, QueryDsl, RunQueryDsl,
+};
+
+use crate::errors::GITrelloError;
+use crate::models::webhook_delivery::{NewWebhookDelivery, WebhookDelivery};
+use crate::state::DbConnection;
+
+pub struct WebhookDeliveryRepository {
+    connection: DbConnection,
+}
+
+impl WebhookDeliveryRepository {
+    pub fn new(connection: DbConnection) -> Self {
+        Self { connection }
+    }
+
+    pub fn create(&self, data: &NewWebhookDelivery) -> Result<WebhookDelivery, GITrelloError> {
+        use crate::schema::webhook_delivery::dsl::*;
+
+        insert_into(webhook_delivery)
+            .values(data)
+            .get_result(&self.connection)
+            .map_err(|source| {
+                match source {
+                    Error::DatabaseError(DatabaseErrorKind::UniqueViolation, error_info) => {
+                        GITrelloError::AlreadyExists { message: String::from(error_info.message()) }
+                    },
+                    _ => GITrelloError::DieselError { source }
+                }
+            })
+    }
+
+    pub fn get_by_id(&self, id: i32) -> Result<WebhookDelivery, GITrelloError> {
+        use crate::schema::webhook_delivery::{table, id as id_column};
+
+        table
+            .filter(id_column.eq(id))
+            .first::<WebhookDelivery>(&self.connection)
+            .map_err(|source| {
+                match source {
+                    Error::NotFound => GITrelloError::NotFound {
+                        message: format!("webhook_delivery {} does not exist", id),
+                    },
+                    _ => GITrelloError::DieselError { source }
+                }
+            })
+    }
+
+    pub fn get_by_delivery_id(&self, delivery_id: &str) -> Result<WebhookDelivery, GITrelloError> {
+        use crate::schema::webhook_delivery::{table, delivery_id as delivery_id_column};
+
+        table
+            .filter(delivery_id_column.eq(delivery_id))
+            .first::<WebhookDelivery>(&self.connection)
+            .map_err(|source| {
+                match source {
+                    Error::NotFound => GITrelloError::NotFound {
+                        message: format!("webhook_delivery {} does not exist", delivery_id),
+                    },
+                    _ => GITrelloError::DieselError { source }
+                }
+            })
+    }
+
+    pub fn get_by_repository_owner_and_name(
+        &self,
+        repository_owner: &str,
+        repository_name: &str,
+        limit: i64,
+    ) -> Result<Vec<WebhookDelivery>, GITrelloError>
+    {
+        use crate::schema::webhook_delivery::{
+            table, received_at, repository_owner as repository_owner_column,
+            repository_name as repository_name_column,
+        };
+
+        table
+            .filter(
+                repository_owner_column.eq(repository_owner)
+                    .and(repository_name_column.eq(repository_name)),
+            )
+            .order(received_at.desc())
+            .limit(limit)
+            .load::<WebhookDelivery>(&self.connection)
+            .map_err(|source| GITrelloError::DieselError { source })
+    }
+
+    pub fn get_by_repository_owner_and_name_and_received_at(
+        &self,
+        repository_owner: &str,
+        repository_name: &str,
+        received_from: DateTime<Utc>,
+        received_to: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<WebhookDelivery>, GITrelloError>
+    {
+        use crate::schema::webhook_delivery::{
+            table, received_at, repository_owner as repository_owner_column,
+            repository_name as repository_name_column,
+        };
+
+        table
+            .filter(
+                repository_owner_column.eq(repository_owner)
+                    .and(repository_name_column.eq(repository_name))
+                    .and(received_at.ge(received_from))
+                    .and(received_at.le(received_to)),
+            )
+            .order(received_at.asc())
+            .limit(limit)
+            .load::<WebhookDelivery>(&self.connection)
+            .map_err(|source| GITrelloError::DieselError { source })
+    }
+
+    pub fn update_status(
+        &self,
+        webhook_delivery: &WebhookDelivery,
+        status: &str,
+        error: Option<&str>,
+    ) -> Result<WebhookDelivery, GITrelloError>
+    {
+        use crate::schema::webhook_delivery::{status as status_column, error as error_column};
+
+        update(webhook_delivery)
+            .set((status_column.eq(status), error_column.eq(error)))
+            .get_result::<WebhookDelivery>(&self.connection)
+            .map_err(|source| {
+                match source {
+                    Error::NotFound => GITrelloError::NotFound {
+                        message: format!("webhook_delivery {} does not exist", webhook_delivery.id),
+                    },
+                    _ => GITrelloError::DieselError { source }
+                }
+            })
+    }
+}
+
+impl Actor for WebhookDeliveryRepository {
+    type Context = Context<Self>;
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<WebhookDelivery, GITrelloError>")]
+pub struct CreateWebhookDeliveryMessage {
+    pub data: NewWebhookDelivery,
+}
+
+impl Handler<CreateWebhookDeliveryMessage> for WebhookDeliveryRepository {
+    type Result = Result<WebhookDelivery, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: CreateWebhookDeliveryMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.create(&msg.data)
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<WebhookDelivery, GITrelloError>")]
+pub struct GetWebhookDeliveryByIdMessage {
+    pub id: i32,
+}
+
+impl Handler<GetWebhookDeliveryByIdMessage> for WebhookDeliveryRepository {
+    type Result = Result<WebhookDelivery, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: GetWebhookDeliveryByIdMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.get_by_id(msg.id)
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<WebhookDelivery, GITrelloError>")]
+pub struct GetWebhookDeliveryByDeliveryIdMessage {
+    pub delivery_id: String,
+}
+
+impl Handler<GetWebhookDeliveryByDeliveryIdMessage> for WebhookDeliveryRepository {
+    type Result = Result<WebhookDelivery, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: GetWebhookDeliveryByDeliveryIdMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.get_by_delivery_id(msg.delivery_id.as_str())
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<Vec<WebhookDelivery>, GITrelloError>")]
+pub struct GetWebhookDeliveriesByRepositoryOwnerAndNameMessage {
+    pub repository_owner: String,
+    pub repository_name: String,
+    pub limit: i64,
+}
+
+impl Handler<GetWebhookDeliveriesByRepositoryOwnerAndNameMessage> for WebhookDeliveryRepository {
+    type Result = Result<Vec<WebhookDelivery>, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: GetWebhookDeliveriesByRepositoryOwnerAndNameMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.get_by_repository_owner_and_name(
+            msg.repository_owner.as_str(),
+            msg.repository_name.as_str(),
+            msg.limit,
+        )
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<Vec<WebhookDelivery>, GITrelloError>")]
+pub struct GetWebhookDeliveriesByRepositoryOwnerAndNameAndReceivedAtMessage {
+    pub repository_owner: String,
+    pub repository_name: String,
+    pub received_from: DateTime<Utc>,
+    pub received_to: DateTime<Utc>,
+    pub limit: i64,
+}
+
+impl Handler<GetWebhookDeliveriesByRepositoryOwnerAndNameAndReceivedAtMessage> for WebhookDeliveryRepository {
+    type Result = Result<Vec<WebhookDelivery>, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: GetWebhookDeliveriesByRepositoryOwnerAndNameAndReceivedAtMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.get_by_repository_owner_and_name_and_received_at(
+            msg.repository_owner.as_str(),
+            msg.repository_name.as_str(),
+            msg.received_from,
+            msg.received_to,
+            msg.limit,
+        )
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "Result<WebhookDelivery, GITrelloError>")]
+pub struct UpdateWebhookDeliveryStatusMessage {
+    pub webhook_delivery: WebhookDelivery,
+    pub status: String,
+    pub error: Option<String>,
+}
+
+impl Handler<UpdateWebhookDeliveryStatusMessage> for WebhookDeliveryRepository {
+    type Result = Result<WebhookDelivery, GITrelloError>;
+
+    fn handle(
+        &mut self,
+        msg: UpdateWebhookDeliveryStatusMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result
+    {
+        self.update_status(&msg.webhook_delivery, msg.status.as_str(), msg.error.as_deref())
+    }
+}
@@ -0,0 +1,373 @@
+use actix::{Actor, Addr, Context, Handler};
+use actix_web::web::Data;
+use chrono::{Duration, Utc};
+
+use crate::errors::GITrelloError;
+use crate::models::board_repository::BoardRepository;
+use crate::models::issue_ticket::IssueTicket;
+use crate::models::ticket_outbox::{
+    NewTicketOutboxItem, TicketOutboxItem, STATUS_DEAD, STATUS_PENDING,
+};
+#[cfg(test)]
+use crate::services::fakes::{FakeGITrelloAPIClient, FakeIssueTicketRepository, GITrelloAPICall};
+use crate::services::gitrello_api_client::{get_gitrello_api_client, GITrelloAPI};
+use crate::services::issue_ticket_service::IssueTicketService;
+use crate::services::repositories::issue_ticket::{
+    CreateIssueTicketMessage, DeleteIssueTicketMessage, GetIssueTicketsByBoardRepositoryIdsAndIssueIdMessage,
+    GetIssueTicketsByBoardRepositoryIdsAndIssueNumberMessage, GetIssueTicketsByTicketIdMessage,
+    UpdateIssueTicketTicketIdMessage,
+};
+use crate::services::repositories::ticket_outbox::{
+    CreateTicketOutboxItemMessage, DeleteTicketOutboxItemMessage, GetDueTicketOutboxItemsMessage,
+    GetTicketOutboxItemByIdMessage, GetTicketOutboxItemsByStatusMessage, TicketOutboxRepository,
+    UpdateTicketOutboxItemAttemptMessage,
+};
+use crate::state::State;
+
+pub const MAX_ATTEMPTS: i32 = 8;
+const BASE_BACKOFF_SECONDS: i64 = 30;
+const MAX_BACKOFF_SECONDS: i64 = 60 * 60;
+const BATCH_SIZE: i64 = 50;
+const LIST_LIMIT: i64 = 100;
+
+// Delay before the next attempt grows exponentially with the number of failed attempts
+pub fn get_backoff(attempts: i32) -> Duration {
+    let exponent = (attempts.max(1) - 1).min(16) as u32;
+    let seconds = BASE_BACKOFF_SECONDS.saturating_mul(2_i64.pow(exponent));
+    Duration::seconds(seconds.min(MAX_BACKOFF_SECONDS))
+}
+
+// Same key is sent on every attempt, so GITrello does not create a second ticket for the same issue
+pub fn get_idempotency_key(board_repository_id: i32, issue_id: i64) -> String {
+    format!("board-repository-{}-issue-{}", board_repository_id, issue_id)
+}
+
+// Only failures that leave the ticket uncreated (or safe to create again thanks to the idempotency key) and
+// failures of our own storage are worth retrying, rejected requests would fail the same way every time
+pub fn is_retryable(error: &GITrelloError) -> bool {
+    match error {
+        GITrelloError::DieselError { source: _ } => true,
+        GITrelloError::R2D2Error { source: _ } => true,
+        GITrelloError::ActorError { source: _ } => true,
+        GITrelloError::HttpRequestError { source: _ } => true,
+        GITrelloError::HttpTimeoutError { source: _ } => true,
+        GITrelloError::GITrelloAPIClientError { message: _, status_code } => *status_code >= 500,
+        _ => false,
+    }
+}
+
+const MAX_MAPPING_ATTEMPTS: u32 = 2;
+
+// A failed write of the mapping may succeed on the next attempt, anything else fails the same way again
+fn is_transient(error: &GITrelloError) -> bool {
+    matches!(
+        error,
+        GITrelloError::DieselError { .. } | GITrelloError::R2D2Error { .. } | GITrelloError::ActorError { .. }
+    )
+}
+
+// An unmapped ticket would be duplicated by a redelivery, so it is archived when the mapping can not be stored.
+// When another delivery has mapped the issue meanwhile, the ticket created now is the duplicate.
+pub async fn map_created_ticket<A>(
+    issue_ticket_service: &IssueTicketService<A>,
+    gitrello_api_client: &dyn GITrelloAPI,
+    board_repository_id: i32,
+    issue_id: i64,
+    issue_number: i32,
+    ticket_id: i64,
+) -> Result<IssueTicket, GITrelloError>
+    where A: Actor<Context = Context<A>>
+        + Handler<CreateIssueTicketMessage>
+        + Handler<GetIssueTicketsByBoardRepositoryIdsAndIssueIdMessage>
+        + Handler<GetIssueTicketsByBoardRepositoryIdsAndIssueNumberMessage>
+        + Handler<GetIssueTicketsByTicketIdMessage>
+        + Handler<UpdateIssueTicketTicketIdMessage>
+        + Handler<DeleteIssueTicketMessage>
+{
+    let mut attempts = 0;
+    let result = loop {
+        attempts += 1;
+        let result = issue_ticket_service
+            .create(board_repository_id, issue_id, issue_number, ticket_id)
+            .await;
+        match &result {
+            Err(e) if is_transient(e) && attempts < MAX_MAPPING_ATTEMPTS => continue,
+            _ => break result,
+        }
+    };
+
+    match &result {
+        Ok(_) => (),
+        Err(GITrelloError::AlreadyExists { message: _ }) => {
+            warn!(
+                "Issue {} of board repository {} already has a ticket, archiving duplicate ticket {}",
+                issue_id,
+                board_repository_id,
+                ticket_id,
+            );
+            let body = format!(
+                "Issue #{} is already linked to another ticket on this board, so this duplicate has been archived.",
+                issue_number,
+            );
+            if let Err(e) = gitrello_api_client.create_ticket_comment(ticket_id, body.as_str()).await {
+                error!("Failed to comment on duplicate ticket {}: {}", ticket_id, e);
+            }
+            if let Err(e) = gitrello_api_client.archive_ticket(ticket_id).await {
+                error!("Failed to archive duplicate ticket {}: {}", ticket_id, e);
+            }
+        },
+        Err(e) => {
+            error!(
+                "Failed to map ticket {} to issue {} of board repository {}: {}",
+                ticket_id,
+                issue_id,
+                board_repository_id,
+                e,
+            );
+            if let Err(e) = gitrello_api_client.archive_ticket(ticket_id).await {
+                error!("Failed to archive unmapped ticket {}: {}", ticket_id, e);
+            }
+        },
+    }
+
+    result
+}
+
+pub struct TicketOutboxService<'a> {
+    state: &'a Data<State>,
+    actor: Addr<TicketOutboxRepository>,
+}
+
+impl<'a> TicketOutboxService<'a> {
+    pub fn new(state: &'a Data<State>) -> Result<Self, GITrelloError> {
+        let connection = state.get_db_connection()?;
+        let actor = TicketOutboxRepository::new(connection).start();
+        Ok(Self { state, actor })
+    }
+
+    pub async fn enqueue(
+        &self,
+        board_repository: &BoardRepository,
+        issue_id: i64,
+        issue_number: i32,
+        title: &str,
+        body: &str,
+        error: &GITrelloError,
+    ) -> Result<TicketOutboxItem, GITrelloError>
+    {
+        warn!(
+            "Failed to create ticket on board {}, scheduling retry: {}",
+            board_repository.board_id,
+            error,
+        );
+
+        self.actor
+            .send(CreateTicketOutboxItemMessage {
+                data: NewTicketOutboxItem {
+                    board_repository_id: board_repository.id,
+                    board_id: board_repository.board_id,
+                    title: title.to_string(),
+                    body: body.to_string(),
+                    status: String::from(STATUS_PENDING),
+                    attempts: 1,
+                    next_attempt_at: Utc::now() + get_backoff(1),
+                    last_error: Some(error.to_string()),
+                    issue_id: Some(issue_id),
+                    issue_number: Some(issue_number),
+                },
+            })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })?
+    }
+
+    pub async fn retry_due(&self) -> Result<(), GITrelloError> {
+        let ticket_outbox_items: Vec<TicketOutboxItem> = self.actor
+            .send(GetDueTicketOutboxItemsMessage { now: Utc::now(), limit: BATCH_SIZE })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })??;
+
+        let gitrello_api_client = get_gitrello_api_client(self.state);
+
+        let issue_ticket_service = IssueTicketService::new(self.state)?;
+
+        for ticket_outbox_item in ticket_outbox_items {
+            let result = gitrello_api_client
+                .create_ticket(
+                    ticket_outbox_item.board_id,
+                    ticket_outbox_item.title.as_str(),
+                    ticket_outbox_item.body.as_str(),
+                    ticket_outbox_item.issue_id
+                        .map(|issue_id| get_idempotency_key(ticket_outbox_item.board_repository_id, issue_id))
+                        .as_deref(),
+                )
+                .await;
+
+            match result {
+                Ok(ticket) => {
+                    // The item is kept until the ticket is mapped, so a failed mapping is retried as well
+                    if let (Some(issue_id), Some(issue_number)) =
+                            (ticket_outbox_item.issue_id, ticket_outbox_item.issue_number) {
+                        let result = map_created_ticket(
+                            &issue_ticket_service,
+                            gitrello_api_client.as_ref(),
+                            ticket_outbox_item.board_repository_id,
+                            issue_id,
+                            issue_number,
+                            ticket.id,
+                        )
+                            .await;
+
+                        match result {
+                            // Issue already has a ticket, the one created now has been archived
+                            Err(GITrelloError::AlreadyExists { message: _ }) => (),
+                            Err(e) => {
+                                self.record_failure(ticket_outbox_item, &e).await?;
+                                continue;
+                            },
+                            Ok(_) => (),
+                        }
+                    }
+
+                    info!("Ticket outbox item {} has been delivered", ticket_outbox_item.id);
+                    self.delete(ticket_outbox_item.id).await?;
+                },
+                Err(e) => {
+                    self.record_failure(ticket_outbox_item, &e).await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_by_status(&self, status: &str) -> Result<Vec<TicketOutboxItem>, GITrelloError> {
+        self.actor
+            .send(GetTicketOutboxItemsByStatusMessage { status: status.to_string(), limit: LIST_LIMIT })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })?
+    }
+
+    // Moves dead-lettered item back to the queue, so it is retried as soon as possible
+    pub async fn requeue(&self, id: i32) -> Result<TicketOutboxItem, GITrelloError> {
+        let ticket_outbox_item: TicketOutboxItem = self.actor
+            .send(GetTicketOutboxItemByIdMessage { id })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })??;
+
+        let last_error = ticket_outbox_item.last_error.clone();
+
+        self.actor
+            .send(UpdateTicketOutboxItemAttemptMessage {
+                ticket_outbox_item,
+                status: String::from(STATUS_PENDING),
+                attempts: 0,
+                next_attempt_at: Utc::now(),
+                last_error,
+            })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })?
+    }
+
+    async fn record_failure(
+        &self,
+        ticket_outbox_item: TicketOutboxItem,
+        error: &GITrelloError,
+    ) -> Result<TicketOutboxItem, GITrelloError>
+    {
+        let attempts = ticket_outbox_item.attempts + 1;
+        let status = match attempts >= MAX_ATTEMPTS || !is_retryable(error) {
+            true => {
+                error!(
+                    "Ticket outbox item {} has failed after {} attempts, moving to dead letters: {}",
+                    ticket_outbox_item.id,
+                    attempts,
+                    error,
+                );
+                STATUS_DEAD
+            },
+            false => STATUS_PENDING,
+        };
+
+        self.actor
+            .send(UpdateTicketOutboxItemAttemptMessage {
+                ticket_outbox_item,
+                status: String::from(status),
+                attempts,
+                next_attempt_at: Utc::now() + get_backoff(attempts),
+                last_error: Some(error.to_string()),
+            })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })?
+    }
+
+    async fn delete(&self, id: i32) -> Result<(), GITrelloError> {
+        self.actor
+            .send(DeleteTicketOutboxItemMessage { id })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })?
+    }
+}
+
+#[test]
+fn test_get_backoff() {
+    assert_eq!(get_backoff(1), Duration::seconds(30));
+    assert_eq!(get_backoff(2), Duration::seconds(60));
+    assert_eq!(get_backoff(3), Duration::seconds(120));
+    assert_eq!(get_backoff(0), Duration::seconds(30));
+}
+
+#[test]
+fn test_get_backoff_is_capped() {
+    assert_eq!(get_backoff(8), Duration::seconds(60 * 60));
+    assert_eq!(get_backoff(i32::MAX), Duration::seconds(60 * 60));
+}
+
+#[test]
+fn test_get_idempotency_key() {
+    assert_eq!(get_idempotency_key(1, 42), "board-repository-1-issue-42");
+}
+
+#[test]
+fn test_is_retryable() {
+    let server_error = GITrelloError::GITrelloAPIClientError { message: String::from("Error"), status_code: 503 };
+    let client_error = GITrelloError::GITrelloAPIClientError { message: String::from("Error"), status_code: 400 };
+
+    assert!(is_retryable(&server_error));
+    assert!(!is_retryable(&client_error));
+    assert!(!is_retryable(&GITrelloError::NotFound { message: String::from("Board not found") }));
+}
+
+#[actix_rt::test]
+async fn test_map_created_ticket() {
+    let gitrello = FakeGITrelloAPIClient::default();
+    let issue_ticket_service = IssueTicketService::with_actor(FakeIssueTicketRepository::default().start());
+
+    let issue_ticket = map_created_ticket(&issue_ticket_service, &gitrello, 1, 42, 7, 10).await.unwrap();
+
+    assert_eq!((issue_ticket.issue_id, issue_ticket.ticket_id), (42, 10));
+    assert_eq!(gitrello.get_calls(), vec![]);
+}
+
+#[actix_rt::test]
+async fn test_map_created_ticket_archives_duplicate() {
+    let gitrello = FakeGITrelloAPIClient::default();
+    let mut repository = FakeIssueTicketRepository::default();
+    repository.add_issue_ticket(1, 42, 7, 20);
+    let issue_ticket_service = IssueTicketService::with_actor(repository.start());
+
+    let result = map_created_ticket(&issue_ticket_service, &gitrello, 1, 42, 7, 10).await;
+
+    assert!(matches!(result, Err(GITrelloError::AlreadyExists { .. })));
+    assert_eq!(
+        gitrello.get_calls(),
+        vec![
+            GITrelloAPICall::CreateTicketComment {
+                ticket_id: 10,
+                body: String::from(
+                    "Issue #7 is already linked to another ticket on this board, so this duplicate has been archived.",
+                ),
+            },
+            GITrelloAPICall::ArchiveTicket { ticket_id: 10 },
+        ],
+    );
+}
@@ -0,0 +1,54 @@
+use std::time::Duration;
+
+use actix::{Actor, ActorFuture, AsyncContext, Context, WrapFuture};
+use actix_web::web::Data;
+
+use crate::services::ticket_outbox_service::TicketOutboxService;
+use crate::state::State;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+pub struct TicketOutboxWorker {
+    state: Data<State>,
+    is_running: bool,
+}
+
+impl TicketOutboxWorker {
+    pub fn new(state: Data<State>) -> Self {
+        Self { state, is_running: false }
+    }
+
+    fn retry_due(&mut self, ctx: &mut Context<Self>) {
+        // Previous batch is still being processed
+        if self.is_running {
+            return;
+        }
+        self.is_running = true;
+
+        let state = self.state.clone();
+        let future = async move {
+            let result = match TicketOutboxService::new(&state) {
+                Ok(ticket_outbox_service) => ticket_outbox_service.retry_due().await,
+                Err(e) => Err(e),
+            };
+
+            if let Err(e) = result {
+                error!("Failed to retry ticket outbox items: {}", e);
+            }
+        };
+
+        ctx.spawn(
+            future
+                .into_actor(self)
+                .map(|_, worker, _ctx| worker.is_running = false),
+        );
+    }
+}
+
+impl Actor for TicketOutboxWorker {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        ctx.run_interval(POLL_INTERVAL, |worker, ctx| worker.retry_due(ctx));
+    }
+}
@@ -0,0 +1,140 @@
+use regex::{Captures, Regex};
+
+use crate::errors::GITrelloError;
+use crate::models::board_repository::BoardRepository;
+use crate::value_objects::github_api::{Issue, PullRequest};
+
+pub const DEFAULT_TITLE_TEMPLATE: &str = "{title}";
+pub const DEFAULT_BODY_TEMPLATE: &str = "{body}\n\n---\nOpened by **{author}** in [{repository}#{number}]({url})";
+pub const PLACEHOLDERS: [&str; 7] = ["repository", "number", "title", "body", "author", "labels", "url"];
+
+const PLACEHOLDER_PATTERN: &str = r"\{([a-z_]+)\}";
+
+pub struct TicketTemplateContext {
+    pub repository: String,
+    pub number: i32,
+    pub title: String,
+    pub body: String,
+    pub author: String,
+    pub labels: Vec<String>,
+    pub url: String,
+}
+
+impl TicketTemplateContext {
+    pub fn from_issue(repository: &str, issue: &Issue) -> Self {
+        Self {
+            repository: repository.to_string(),
+            number: issue.number,
+            title: issue.title.clone(),
+            body: issue.body.clone().unwrap_or_default(),
+            author: issue.user.login.clone(),
+            labels: issue.labels.iter().map(|label| label.name.clone()).collect(),
+            url: issue.html_url.clone(),
+        }
+    }
+
+    pub fn from_pull_request(repository: &str, pull_request: &PullRequest) -> Self {
+        Self {
+            repository: repository.to_string(),
+            number: pull_request.number,
+            title: pull_request.title.clone(),
+            body: pull_request.body.clone().unwrap_or_default(),
+            author: pull_request.user.login.clone(),
+            labels: vec![],
+            url: pull_request.html_url.clone(),
+        }
+    }
+
+    // Used to preview templates before any issue is opened
+    pub fn sample(repository: &str) -> Self {
+        Self {
+            repository: repository.to_string(),
+            number: 42,
+            title: String::from("Login button does nothing"),
+            body: String::from("Clicking the login button on the main page does not open the login form."),
+            author: String::from("octocat"),
+            labels: vec![String::from("bug"), String::from("good first issue")],
+            url: format!("https://github.com/{}/issues/42", repository),
+        }
+    }
+
+    fn get(&self, placeholder: &str) -> Option<String> {
+        match placeholder {
+            "repository" => Some(self.repository.clone()),
+            "number" => Some(self.number.to_string()),
+            "title" => Some(self.title.clone()),
+            "body" => Some(self.body.clone()),
+            "author" => Some(self.author.clone()),
+            "labels" => Some(self.labels.join(", ")),
+            "url" => Some(self.url.clone()),
+            _ => None,
+        }
+    }
+}
+
+pub fn validate_template(template: &str) -> Result<(), GITrelloError> {
+    let regex = Regex::new(PLACEHOLDER_PATTERN).expect("pattern should be valid");
+
+    for captures in regex.captures_iter(template) {
+        if !PLACEHOLDERS.contains(&&captures[1]) {
+            return Err(GITrelloError::BadRequest {
+                message: format!(
+                    "{{{}}} is not a valid placeholder, expected one of {}",
+                    &captures[1],
+                    PLACEHOLDERS.join(", "),
+                ),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+// Unknown placeholders are left as they are
+pub fn render_template(template: &str, context: &TicketTemplateContext) -> String {
+    let regex = Regex::new(PLACEHOLDER_PATTERN).expect("pattern should be valid");
+
+    regex
+        .replace_all(template, |captures: &Captures| {
+            context.get(&captures[1]).unwrap_or_else(|| captures[0].to_string())
+        })
+        .into_owned()
+}
+
+// Tickets can not have an empty title, the original one is used when the template renders to nothing
+pub fn render_ticket(board_repository: &BoardRepository, context: &TicketTemplateContext) -> (String, String) {
+    let title_template = board_repository.ticket_title_template.as_deref().unwrap_or(DEFAULT_TITLE_TEMPLATE);
+    let body_template = board_repository.ticket_body_template.as_deref().unwrap_or(DEFAULT_BODY_TEMPLATE);
+
+    let title = render_template(title_template, context);
+    let title = match title.trim().is_empty() {
+        true => context.title.clone(),
+        false => title,
+    };
+
+    (title, render_template(body_template, context))
+}
+
+#[test]
+fn test_render_template() {
+    let context = TicketTemplateContext::sample("octocat/hello-world");
+
+    assert_eq!(
+        render_template("[{repository}#{number}] {title}", &context),
+        "[octocat/hello-world#42] Login button does nothing",
+    );
+    assert_eq!(render_template("{author}: {labels}", &context), "octocat: bug, good first issue");
+    assert_eq!(render_template("{unknown} {url}", &context), "{unknown} https://github.com/octocat/hello-world/issues/42");
+    assert_eq!(
+        render_template(DEFAULT_BODY_TEMPLATE, &context),
+        "Clicking the login button on the main page does not open the login form.\n\n---\n\
+        Opened by **octocat** in [octocat/hello-world#42](https://github.com/octocat/hello-world/issues/42)",
+    );
+}
+
+#[test]
+fn test_validate_template() {
+    assert!(validate_template("{title} ({labels})").is_ok());
+    assert!(validate_template("No placeholders at all").is_ok());
+    assert!(validate_template("{title} by {user}").is_err());
+}
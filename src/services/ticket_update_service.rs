@@ -0,0 +1,131 @@
+use actix::{Actor, Addr};
+use actix_web::web::Data;
+
+use crate::errors::GITrelloError;
+use crate::models::board_repository::BoardRepository;
+use crate::models::column_state::{STATE_CLOSED, STATE_OPEN};
+use crate::models::issue_ticket::IssueTicket;
+use crate::services::column_state_service::{get_issue_state, ColumnStateService};
+use crate::services::github_api_client::get_github_api_client;
+use crate::services::github_profile_service::GithubProfileService;
+use crate::services::issue_ticket_service::IssueTicketService;
+use crate::services::repositories::board_repository::{
+    BoardRepositoryRepository, GetBoardRepositoryByIdMessage,
+};
+use crate::services::ticket_template::DEFAULT_TITLE_TEMPLATE;
+use crate::state::State;
+
+pub struct TicketUpdateService<'a> {
+    state: &'a Data<State>,
+    actor: Addr<BoardRepositoryRepository>,
+}
+
+impl<'a> TicketUpdateService<'a> {
+    pub fn new(state: &'a Data<State>) -> Result<Self, GITrelloError> {
+        let connection = state.get_db_connection()?;
+        let actor = BoardRepositoryRepository::new(connection).start();
+        Ok(Self { state, actor })
+    }
+
+    // Applies a ticket update reported by GITrello to every issue mapped to the ticket
+    pub async fn apply(
+        &self,
+        ticket_id: i64,
+        title: Option<&str>,
+        column_id: Option<i64>,
+    ) -> Result<(), GITrelloError>
+    {
+        let issue_ticket_service = IssueTicketService::new(self.state)?;
+        let issue_tickets = issue_ticket_service.get_by_ticket_id(ticket_id).await?;
+
+        let github_profile_service = GithubProfileService::new(self.state)?;
+        let column_state_service = ColumnStateService::new(self.state)?;
+
+        let mut errors = Vec::new();
+        for issue_ticket in issue_tickets.iter() {
+            let result = self
+                .apply_to_issue(issue_ticket, title, column_id, &github_profile_service, &column_state_service)
+                .await;
+
+            if let Err(e) = result {
+                error!("Failed to apply update of ticket {} to issue {}: {}", ticket_id, issue_ticket.issue_id, e);
+                errors.push(e);
+            }
+        }
+
+        GITrelloError::aggregate(errors)
+    }
+
+    async fn apply_to_issue(
+        &self,
+        issue_ticket: &IssueTicket,
+        title: Option<&str>,
+        column_id: Option<i64>,
+        github_profile_service: &GithubProfileService<'_>,
+        column_state_service: &ColumnStateService,
+    ) -> Result<(), GITrelloError>
+    {
+        let board_repository: BoardRepository = self.actor
+            .send(GetBoardRepositoryByIdMessage { id: issue_ticket.board_repository_id })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })??;
+
+        if board_repository.is_read_only {
+            return Ok(());
+        }
+
+        let github_profile = github_profile_service
+            .get_by_id(board_repository.github_profile_id)
+            .await?;
+        let github_api_client = get_github_api_client(self.state, &github_profile);
+
+        let repository_name = board_repository.repository_name.as_str();
+        let repository_owner = board_repository.repository_owner.as_str();
+
+        // Rendered titles contain more than the issue title, they would compound on every round trip
+        let title = title.filter(|_| has_default_title_template(&board_repository));
+        if let Some(title) = title {
+            github_api_client
+                .update_issue_title(repository_name, repository_owner, issue_ticket.issue_number, title)
+                .await?;
+        }
+
+        if let Some(column_id) = column_id {
+            let column_states = column_state_service.get_by_board_repository(&board_repository).await?;
+
+            match get_issue_state(&column_states, column_id) {
+                Some(STATE_CLOSED) => {
+                    github_api_client
+                        .close_issue(repository_name, repository_owner, issue_ticket.issue_number)
+                        .await?;
+                },
+                Some(STATE_OPEN) => {
+                    github_api_client
+                        .reopen_issue(repository_name, repository_owner, issue_ticket.issue_number)
+                        .await?;
+                },
+                _ => ()
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub fn has_default_title_template(board_repository: &BoardRepository) -> bool {
+    board_repository.ticket_title_template.as_deref().unwrap_or(DEFAULT_TITLE_TEMPLATE) == DEFAULT_TITLE_TEMPLATE
+}
+
+#[test]
+fn test_has_default_title_template() {
+    use crate::services::fakes::get_fake_board_repository;
+
+    let mut board_repository = get_fake_board_repository(1, "backend", &[]);
+    assert!(has_default_title_template(&board_repository));
+
+    board_repository.ticket_title_template = Some(String::from(DEFAULT_TITLE_TEMPLATE));
+    assert!(has_default_title_template(&board_repository));
+
+    board_repository.ticket_title_template = Some(String::from("[{repository}] {title}"));
+    assert!(!has_default_title_template(&board_repository));
+}
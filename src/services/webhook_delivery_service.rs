@@ -0,0 +1,205 @@
+use std::collections::BTreeMap;
+
+use actix::{Actor, Addr};
+use actix_web::http::HeaderMap;
+use actix_web::web::{BytesMut, Data};
+use chrono::{DateTime, Utc};
+
+use crate::errors::GITrelloError;
+use crate::models::board_repository::BoardRepository;
+use crate::models::webhook_delivery::{
+    NewWebhookDelivery, WebhookDelivery, STATUS_FAILED, STATUS_PROCESSED, STATUS_RECEIVED,
+};
+use crate::services::repositories::webhook_delivery::{
+    CreateWebhookDeliveryMessage, GetWebhookDeliveriesByRepositoryOwnerAndNameAndReceivedAtMessage,
+    GetWebhookDeliveriesByRepositoryOwnerAndNameMessage, GetWebhookDeliveryByDeliveryIdMessage,
+    GetWebhookDeliveryByIdMessage, UpdateWebhookDeliveryStatusMessage, WebhookDeliveryRepository,
+};
+use crate::services::github_webhook_service::GithubWebhookProcessingService;
+use crate::state::State;
+use crate::value_objects::request_data::webhook::WebhookEnvelope;
+
+const WEBHOOK_DELIVERIES_LIMIT: i64 = 100;
+const REPLAY_LIMIT: i64 = 500;
+
+// Headers are stored as a JSON object, so deliveries can be inspected the way GitHub sent them
+pub fn serialize_headers(headers: &HeaderMap) -> String {
+    let headers: BTreeMap<String, String> = headers
+        .iter()
+        .filter_map(|(name, value)| value.to_str().ok().map(|value| (name.to_string(), value.to_string())))
+        .collect();
+
+    serde_json::to_string(&headers).expect("map of strings can always be serialized")
+}
+
+pub struct WebhookDeliveryService {
+    actor: Addr<WebhookDeliveryRepository>,
+}
+
+impl WebhookDeliveryService {
+    pub fn new(state: &Data<State>) -> Result<Self, GITrelloError> {
+        let connection = state.get_db_connection()?;
+        let actor = WebhookDeliveryRepository::new(connection).start();
+        Ok(Self { actor })
+    }
+
+    // Returns existing delivery if GitHub has already sent it, otherwise records a new one
+    pub async fn receive(
+        &self,
+        delivery_id: &str,
+        event: &str,
+        request_body: &[u8],
+        headers: &HeaderMap,
+    ) -> Result<WebhookDelivery, GITrelloError>
+    {
+        let existing_webhook_delivery = self.get_by_delivery_id(delivery_id).await;
+        match existing_webhook_delivery {
+            Err(GITrelloError::NotFound { message: _ }) => (),
+            _ => return existing_webhook_delivery,
+        }
+
+        let envelope = serde_json::from_slice::<WebhookEnvelope>(request_body).ok();
+        let (action, repository_owner, repository_name) = match envelope {
+            Some(envelope) => {
+                let repository = envelope.repository.as_ref().map(|repository| repository.split_full_name());
+                (
+                    envelope.action.clone(),
+                    repository.map(|parts| parts.0.to_string()),
+                    repository.map(|parts| parts.1.to_string()),
+                )
+            },
+            None => (None, None, None),
+        };
+
+        let result = self.actor
+            .send(CreateWebhookDeliveryMessage {
+                data: NewWebhookDelivery {
+                    delivery_id: delivery_id.to_string(),
+                    event: event.to_string(),
+                    action,
+                    repository_owner,
+                    repository_name,
+                    status: String::from(STATUS_RECEIVED),
+                    payload: Some(String::from_utf8_lossy(request_body).into_owned()),
+                    headers: Some(serialize_headers(headers)),
+                },
+            })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })?;
+
+        match result {
+            // The same delivery is being received concurrently
+            Err(GITrelloError::AlreadyExists { message: _ }) => self.get_by_delivery_id(delivery_id).await,
+            _ => result,
+        }
+    }
+
+    pub async fn finish(
+        &self,
+        webhook_delivery: WebhookDelivery,
+        result: &Result<(), GITrelloError>,
+    ) -> Result<WebhookDelivery, GITrelloError>
+    {
+        let (status, error) = match result {
+            Ok(_) => (STATUS_PROCESSED, None),
+            Err(e) => (STATUS_FAILED, Some(e.to_string())),
+        };
+
+        self.actor
+            .send(UpdateWebhookDeliveryStatusMessage {
+                webhook_delivery,
+                status: String::from(status),
+                error,
+            })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })?
+    }
+
+    pub async fn get_by_board_repository(
+        &self,
+        board_repository: &BoardRepository,
+    ) -> Result<Vec<WebhookDelivery>, GITrelloError>
+    {
+        self.actor
+            .send(GetWebhookDeliveriesByRepositoryOwnerAndNameMessage {
+                repository_owner: board_repository.repository_owner.clone(),
+                repository_name: board_repository.repository_name.clone(),
+                limit: WEBHOOK_DELIVERIES_LIMIT,
+            })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })?
+    }
+
+    pub async fn get_by_id(&self, id: i32) -> Result<WebhookDelivery, GITrelloError> {
+        self.actor
+            .send(GetWebhookDeliveryByIdMessage { id })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })?
+    }
+
+    pub async fn get_by_repository_and_received_at(
+        &self,
+        repository_owner: &str,
+        repository_name: &str,
+        received_from: DateTime<Utc>,
+        received_to: DateTime<Utc>,
+    ) -> Result<Vec<WebhookDelivery>, GITrelloError>
+    {
+        if received_from > received_to {
+            return Err(GITrelloError::BadRequest { message: String::from("from must not be later than to") });
+        }
+
+        self.actor
+            .send(GetWebhookDeliveriesByRepositoryOwnerAndNameAndReceivedAtMessage {
+                repository_owner: repository_owner.to_string(),
+                repository_name: repository_name.to_string(),
+                received_from,
+                received_to,
+                limit: REPLAY_LIMIT,
+            })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })?
+    }
+
+    // Feeds the stored payload through processing again, regardless of the previous outcome
+    pub async fn replay(
+        &self,
+        webhook_delivery: WebhookDelivery,
+        processing_service: &GithubWebhookProcessingService<'_>,
+    ) -> Result<WebhookDelivery, GITrelloError>
+    {
+        let payload = match &webhook_delivery.payload {
+            Some(payload) => BytesMut::from(payload.as_bytes()),
+            None => {
+                return Err(GITrelloError::BadRequest {
+                    message: format!("Payload of webhook_delivery {} has not been stored", webhook_delivery.id),
+                });
+            },
+        };
+
+        info!("Replaying delivery {}", webhook_delivery.delivery_id);
+        let result = processing_service.process(webhook_delivery.event.as_str(), payload).await;
+        self.finish(webhook_delivery, &result).await
+    }
+
+    async fn get_by_delivery_id(&self, delivery_id: &str) -> Result<WebhookDelivery, GITrelloError> {
+        self.actor
+            .send(GetWebhookDeliveryByDeliveryIdMessage { delivery_id: delivery_id.to_string() })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })?
+    }
+}
+
+#[test]
+fn test_serialize_headers() {
+    use actix_web::http::header::{HeaderName, HeaderValue};
+
+    let mut headers = HeaderMap::new();
+    headers.insert(HeaderName::from_static("x-github-event"), HeaderValue::from_static("issues"));
+    headers.insert(HeaderName::from_static("content-type"), HeaderValue::from_static("application/json"));
+
+    assert_eq!(
+        serialize_headers(&headers),
+        r#"{"content-type":"application/json","x-github-event":"issues"}"#,
+    );
+}
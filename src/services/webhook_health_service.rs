@@ -0,0 +1,95 @@
+use actix::{Actor, Addr};
+use actix_web::web::Data;
+use chrono::{DateTime, Duration, Utc};
+
+use crate::errors::GITrelloError;
+use crate::models::board_repository::BoardRepository;
+use crate::models::github_webhook::{
+    GithubWebhook, UpdateGithubWebhookHealth, HEALTH_ACTIVE, HEALTH_FAILING, HEALTH_STALE,
+};
+#[cfg(test)]
+use crate::services::fakes::get_fake_github_webhook;
+use crate::services::repositories::github_webhook::{
+    GetByBoardRepositoryIdMessage, GithubWebhookRepository, UpdateHealthMessage,
+};
+use crate::state::State;
+
+const STALE_AFTER_DAYS: i64 = 7;
+
+// Failed last delivery makes the hook failing, no deliveries for a while make it stale
+pub fn get_health_status(github_webhook: &GithubWebhook, now: DateTime<Utc>) -> &'static str {
+    if github_webhook.last_error.is_some() {
+        return HEALTH_FAILING;
+    }
+
+    let last_seen_at = github_webhook.last_delivery_at.max(github_webhook.last_ping_at);
+    match last_seen_at {
+        Some(last_seen_at) if now - last_seen_at <= Duration::days(STALE_AFTER_DAYS) => HEALTH_ACTIVE,
+        _ => HEALTH_STALE,
+    }
+}
+
+pub struct WebhookHealthService {
+    actor: Addr<GithubWebhookRepository>,
+}
+
+impl WebhookHealthService {
+    pub fn new(state: &Data<State>) -> Result<Self, GITrelloError> {
+        let connection = state.get_db_connection()?;
+        let actor = GithubWebhookRepository::new(connection).start();
+        Ok(Self { actor })
+    }
+
+    pub async fn record(
+        &self,
+        webhook_id: i64,
+        event: &str,
+        error: Option<&GITrelloError>,
+    ) -> Result<(), GITrelloError>
+    {
+        let now = Utc::now();
+        let data = UpdateGithubWebhookHealth {
+            last_ping_at: match event {
+                "ping" => Some(now),
+                _ => None,
+            },
+            last_delivery_at: Some(now),
+            last_error: Some(error.map(|e| e.to_string())),
+        };
+
+        self.actor
+            .send(UpdateHealthMessage { webhook_id, data })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })?
+    }
+
+    pub async fn get_by_board_repository(
+        &self,
+        board_repository: &BoardRepository,
+    ) -> Result<GithubWebhook, GITrelloError>
+    {
+        self.actor
+            .send(GetByBoardRepositoryIdMessage { board_repository_id: board_repository.id })
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })?
+    }
+}
+
+#[test]
+fn test_get_health_status() {
+    let now = Utc::now();
+    let mut github_webhook = get_fake_github_webhook(1, 2);
+    assert_eq!(get_health_status(&github_webhook, now), HEALTH_STALE);
+
+    github_webhook.last_ping_at = Some(now - Duration::days(1));
+    assert_eq!(get_health_status(&github_webhook, now), HEALTH_ACTIVE);
+
+    github_webhook.last_ping_at = Some(now - Duration::days(30));
+    assert_eq!(get_health_status(&github_webhook, now), HEALTH_STALE);
+
+    github_webhook.last_delivery_at = Some(now - Duration::hours(1));
+    assert_eq!(get_health_status(&github_webhook, now), HEALTH_ACTIVE);
+
+    github_webhook.last_error = Some(String::from("Internal error"));
+    assert_eq!(get_health_status(&github_webhook, now), HEALTH_FAILING);
+}
@@ -0,0 +1,386 @@
+use std::collections::BTreeMap;
+
+use actix::{Actor, Addr};
+use actix_web::web::Data;
+
+use crate::errors::GITrelloError;
+use crate::models::board_repository::BoardRepository;
+use crate::models::github_webhook::GithubWebhook;
+#[cfg(test)]
+use crate::services::fakes::{get_fake_github_webhook, FAKE_WEBHOOK_URL};
+use crate::services::github_api_client::get_github_api_client;
+use crate::services::github_profile_service::GithubProfileService;
+use crate::services::github_webhook_service::get_webhook_events;
+use crate::services::repositories::github_webhook::{
+    GetAllGithubWebhooksWithBoardRepositoryMessage, GithubWebhookRepository, UpdateWebhookIdMessage,
+};
+use crate::services::webhook_signature::generate_secret;
+use crate::state::State;
+use crate::value_objects::github_api::Webhook;
+
+#[derive(Debug)]
+pub struct WebhookReconciliationReport {
+    pub repository_owner: String,
+    pub repository_name: String,
+    pub missing_webhook: bool,
+    pub outdated_events: bool,
+    pub missing_secret: bool,
+    pub mismatched_github_webhook_ids: Vec<i32>,
+    pub orphaned_webhook_ids: Vec<i64>,
+    pub error: Option<String>,
+}
+
+impl WebhookReconciliationReport {
+    pub fn has_changes(&self) -> bool {
+        self.missing_webhook ||
+            self.outdated_events ||
+            self.missing_secret ||
+            !self.mismatched_github_webhook_ids.is_empty() ||
+            !self.orphaned_webhook_ids.is_empty() ||
+            self.error.is_some()
+    }
+}
+
+#[derive(Debug)]
+pub struct WebhookReconciliationPlan<'a> {
+    // None when no stored hook exists on GitHub with our url, a new one has to be created
+    pub github_webhook: Option<&'a GithubWebhook>,
+    pub outdated_events: bool,
+    // Hooks created before deliveries were signed have no secret, a new one is set on GitHub
+    pub missing_secret: bool,
+    pub mismatched_github_webhooks: Vec<&'a GithubWebhook>,
+    pub orphaned_webhook_ids: Vec<i64>,
+}
+
+pub struct WebhookReconciliationService<'a> {
+    state: &'a Data<State>,
+    actor: Addr<GithubWebhookRepository>,
+}
+
+impl<'a> WebhookReconciliationService<'a> {
+    pub fn new(state: &'a Data<State>) -> Result<Self, GITrelloError> {
+        let connection = state.get_db_connection()?;
+        let actor = GithubWebhookRepository::new(connection).start();
+        Ok(Self { state, actor })
+    }
+
+    // Errors of a single repository are reported and do not stop the rest of reconciliation
+    pub async fn reconcile(&self, is_dry_run: bool) -> Result<Vec<WebhookReconciliationReport>, GITrelloError> {
+        self.reconcile_repositories(is_dry_run, false).await
+    }
+
+    // Deliveries of hooks without a secret are rejected, so these repositories are not left for the next full run
+    pub async fn reconcile_missing_secrets(&self) -> Result<Vec<WebhookReconciliationReport>, GITrelloError> {
+        self.reconcile_repositories(false, true).await
+    }
+
+    async fn reconcile_repositories(
+        &self,
+        is_dry_run: bool,
+        only_missing_secrets: bool,
+    ) -> Result<Vec<WebhookReconciliationReport>, GITrelloError>
+    {
+        let github_webhooks = self.actor
+            .send(GetAllGithubWebhooksWithBoardRepositoryMessage)
+            .await
+            .map_err(|source| GITrelloError::ActorError { source })??;
+
+        let mut github_webhooks_by_repository: BTreeMap<(String, String), Vec<(GithubWebhook, BoardRepository)>> =
+            BTreeMap::new();
+        for (github_webhook, board_repository) in github_webhooks {
+            github_webhooks_by_repository
+                .entry((board_repository.repository_owner.clone(), board_repository.repository_name.clone()))
+                .or_default()
+                .push((github_webhook, board_repository));
+        }
+
+        let mut reports = vec![];
+        for ((repository_owner, repository_name), github_webhooks) in github_webhooks_by_repository {
+            // Hooks of archived repositories can not be changed
+            if github_webhooks.iter().any(|(_, board_repository)| board_repository.is_read_only) {
+                continue;
+            }
+
+            let has_missing_secret = github_webhooks.iter().any(|(github_webhook, _)| github_webhook.secret.is_empty());
+            if only_missing_secrets && !has_missing_secret {
+                continue;
+            }
+
+            let mut report = WebhookReconciliationReport {
+                repository_owner,
+                repository_name,
+                missing_webhook: false,
+                outdated_events: false,
+                missing_secret: false,
+                mismatched_github_webhook_ids: vec![],
+                orphaned_webhook_ids: vec![],
+                error: None,
+            };
+
+            if let Err(e) = self.reconcile_repository(&github_webhooks, &mut report, is_dry_run).await {
+                error!(
+                    "Failed to reconcile webhooks of {}/{}: {}",
+                    report.repository_owner,
+                    report.repository_name,
+                    e,
+                );
+                report.error = Some(e.to_string());
+            }
+
+            reports.push(report);
+        }
+
+        Ok(reports)
+    }
+
+    async fn reconcile_repository(
+        &self,
+        github_webhooks: &[(GithubWebhook, BoardRepository)],
+        report: &mut WebhookReconciliationReport,
+        is_dry_run: bool,
+    ) -> Result<(), GITrelloError>
+    {
+        let github_profile_id = github_webhooks
+            .first()
+            .map(|(_, board_repository)| board_repository.github_profile_id)
+            .expect("can not be empty");
+        let github_profile_service = GithubProfileService::new(self.state)?;
+        let github_profile = github_profile_service.get_by_id(github_profile_id).await?;
+        let github_api_client = get_github_api_client(self.state, &github_profile);
+
+        let repository_name = report.repository_name.as_str();
+        let repository_owner = report.repository_owner.as_str();
+        let webhooks = github_api_client.get_webhooks(repository_name, repository_owner).await?;
+
+        let (github_webhooks, board_repositories): (Vec<GithubWebhook>, Vec<BoardRepository>) = github_webhooks
+            .iter()
+            .cloned()
+            .unzip();
+        let events = get_webhook_events(&board_repositories);
+        let plan = get_reconciliation_plan(&github_webhooks, &webhooks, self.state.webhook_url.as_str(), &events);
+
+        report.missing_webhook = plan.github_webhook.is_none();
+        report.outdated_events = plan.outdated_events;
+        report.missing_secret = plan.missing_secret;
+        report.mismatched_github_webhook_ids = plan.mismatched_github_webhooks
+            .iter()
+            .map(|github_webhook| github_webhook.id)
+            .collect();
+        report.orphaned_webhook_ids = plan.orphaned_webhook_ids.clone();
+
+        if is_dry_run {
+            return Ok(());
+        }
+
+        let (webhook_id, secret) = match plan.github_webhook {
+            Some(github_webhook) if plan.missing_secret => {
+                let secret = generate_secret();
+                github_api_client
+                    .update_webhook_config(
+                        repository_name,
+                        repository_owner,
+                        github_webhook.webhook_id,
+                        self.state.webhook_url.as_str(),
+                        secret.as_str(),
+                    )
+                    .await?;
+                (github_webhook.webhook_id, secret)
+            },
+            Some(github_webhook) => (github_webhook.webhook_id, github_webhook.secret.clone()),
+            None => {
+                let secret = generate_secret();
+                let webhook = github_api_client
+                    .create_webhook(
+                        repository_name,
+                        repository_owner,
+                        self.state.webhook_url.as_str(),
+                        secret.as_str(),
+                        events.clone(),
+                    )
+                    .await?;
+                (webhook.id, secret)
+            },
+        };
+
+        if plan.outdated_events {
+            github_api_client
+                .update_webhook_events(repository_name, repository_owner, webhook_id, events)
+                .await?;
+        }
+
+        for github_webhook in plan.mismatched_github_webhooks {
+            self.actor
+                .send(
+                    UpdateWebhookIdMessage {
+                        github_webhook: github_webhook.clone(),
+                        webhook_id,
+                        url: self.state.webhook_url.clone(),
+                        secret: secret.clone(),
+                    },
+                )
+                .await
+                .map_err(|source| GITrelloError::ActorError { source })??;
+        }
+
+        for orphaned_webhook_id in plan.orphaned_webhook_ids {
+            github_api_client
+                .delete_webhook(repository_name, repository_owner, orphaned_webhook_id)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+// Compares stored hooks of a single repository with the ones that exist on GitHub.
+// Hooks pointing at webhook_url or referenced by stored rows are ours, only one of them is kept.
+pub fn get_reconciliation_plan<'a>(
+    github_webhooks: &'a [GithubWebhook],
+    webhooks: &[Webhook],
+    webhook_url: &str,
+    events: &[String],
+) -> WebhookReconciliationPlan<'a>
+{
+    let has_webhook_url = |webhook: &Webhook| webhook.config.url.as_deref() == Some(webhook_url);
+
+    let github_webhook = github_webhooks
+        .iter()
+        .find(|github_webhook| {
+            webhooks
+                .iter()
+                .any(|webhook| webhook.id == github_webhook.webhook_id && has_webhook_url(webhook))
+        });
+
+    // Hooks created before events were configurable lack some of them, e.g. the repository event
+    let outdated_events = github_webhook
+        .and_then(|github_webhook| webhooks.iter().find(|webhook| webhook.id == github_webhook.webhook_id))
+        .map(|webhook| {
+            let mut webhook_events = webhook.events.clone();
+            webhook_events.sort();
+            webhook_events != events
+        })
+        .unwrap_or(false);
+
+    let missing_secret = github_webhook
+        .map(|github_webhook| github_webhook.secret.is_empty())
+        .unwrap_or(false);
+
+    let mismatched_github_webhooks = github_webhooks
+        .iter()
+        .filter(|stored_github_webhook| match github_webhook {
+            Some(github_webhook) => {
+                missing_secret ||
+                    stored_github_webhook.webhook_id != github_webhook.webhook_id ||
+                    stored_github_webhook.secret != github_webhook.secret ||
+                    stored_github_webhook.url != webhook_url
+            },
+            None => true,
+        })
+        .collect();
+
+    let orphaned_webhook_ids = webhooks
+        .iter()
+        .filter(|webhook| {
+            has_webhook_url(webhook) ||
+                github_webhooks.iter().any(|github_webhook| github_webhook.webhook_id == webhook.id)
+        })
+        .map(|webhook| webhook.id)
+        .filter(|webhook_id| github_webhook.map(|github_webhook| github_webhook.webhook_id) != Some(*webhook_id))
+        .collect();
+
+    WebhookReconciliationPlan {
+        github_webhook,
+        outdated_events,
+        missing_secret,
+        mismatched_github_webhooks,
+        orphaned_webhook_ids,
+    }
+}
+
+#[cfg(test)]
+fn get_test_webhook(id: i64, url: &str) -> Webhook {
+    use crate::value_objects::github_api::WebhookConfig;
+
+    Webhook {
+        id,
+        config: WebhookConfig { url: Some(String::from(url)) },
+        events: get_test_events(),
+    }
+}
+
+#[cfg(test)]
+fn get_test_events() -> Vec<String> {
+    vec![String::from("issues"), String::from("repository")]
+}
+
+#[test]
+fn test_get_reconciliation_plan_in_sync() {
+    let github_webhooks = vec![get_fake_github_webhook(1, 10), get_fake_github_webhook(2, 10)];
+    let webhooks = vec![get_test_webhook(10, FAKE_WEBHOOK_URL), get_test_webhook(20, "https://ci.example.com")];
+
+    let plan = get_reconciliation_plan(&github_webhooks, &webhooks, FAKE_WEBHOOK_URL, &get_test_events());
+    assert_eq!(plan.github_webhook.map(|github_webhook| github_webhook.id), Some(1));
+    assert!(!plan.outdated_events);
+    assert!(!plan.missing_secret);
+    assert!(plan.mismatched_github_webhooks.is_empty());
+    assert!(plan.orphaned_webhook_ids.is_empty());
+}
+
+#[test]
+fn test_get_reconciliation_plan_missing_secret() {
+    let mut github_webhooks = vec![get_fake_github_webhook(1, 10), get_fake_github_webhook(2, 10)];
+    for github_webhook in github_webhooks.iter_mut() {
+        github_webhook.secret = String::new();
+    }
+    let webhooks = vec![get_test_webhook(10, FAKE_WEBHOOK_URL)];
+
+    let plan = get_reconciliation_plan(&github_webhooks, &webhooks, FAKE_WEBHOOK_URL, &get_test_events());
+    assert_eq!(plan.github_webhook.map(|github_webhook| github_webhook.id), Some(1));
+    assert!(plan.missing_secret);
+    assert_eq!(plan.mismatched_github_webhooks.len(), 2);
+    assert!(plan.orphaned_webhook_ids.is_empty());
+}
+
+#[test]
+fn test_get_reconciliation_plan_outdated_events() {
+    let github_webhooks = vec![get_fake_github_webhook(1, 10)];
+    let mut webhook = get_test_webhook(10, FAKE_WEBHOOK_URL);
+    webhook.events = vec![String::from("issues")];
+
+    let events = get_test_events();
+    let plan = get_reconciliation_plan(&github_webhooks, &[webhook], FAKE_WEBHOOK_URL, &events);
+    assert!(plan.outdated_events);
+    assert!(plan.mismatched_github_webhooks.is_empty());
+}
+
+#[test]
+fn test_get_reconciliation_plan_missing_webhook() {
+    let github_webhooks = vec![get_fake_github_webhook(1, 10)];
+    let webhooks = vec![get_test_webhook(20, "https://ci.example.com")];
+
+    let plan = get_reconciliation_plan(&github_webhooks, &webhooks, FAKE_WEBHOOK_URL, &get_test_events());
+    assert!(plan.github_webhook.is_none());
+    assert_eq!(plan.mismatched_github_webhooks.len(), 1);
+    assert!(plan.orphaned_webhook_ids.is_empty());
+}
+
+#[test]
+fn test_get_reconciliation_plan_webhook_id_mismatch_and_orphans() {
+    let mut stale_github_webhook = get_fake_github_webhook(2, 11);
+    stale_github_webhook.url = String::from("https://old.example.com/api/v1/webhook");
+    let github_webhooks = vec![get_fake_github_webhook(1, 10), stale_github_webhook];
+    let webhooks = vec![
+        get_test_webhook(10, FAKE_WEBHOOK_URL),
+        get_test_webhook(11, "https://old.example.com/api/v1/webhook"),
+        get_test_webhook(12, FAKE_WEBHOOK_URL),
+        get_test_webhook(20, "https://ci.example.com"),
+    ];
+
+    let plan = get_reconciliation_plan(&github_webhooks, &webhooks, FAKE_WEBHOOK_URL, &get_test_events());
+    assert_eq!(plan.github_webhook.map(|github_webhook| github_webhook.id), Some(1));
+    assert_eq!(
+        plan.mismatched_github_webhooks.iter().map(|github_webhook| github_webhook.id).collect::<Vec<i32>>(),
+        vec![2],
+    );
+    assert_eq!(plan.orphaned_webhook_ids, vec![11, 12]);
+}
@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use actix::{Actor, ActorFuture, AsyncContext, Context, WrapFuture};
+use actix_web::web::Data;
+
+use crate::services::webhook_reconciliation_service::WebhookReconciliationService;
+use crate::state::State;
+
+const RECONCILIATION_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+// Hooks without a secret reject every delivery, so setting one is retried much more often
+const MISSING_SECRETS_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+pub struct WebhookReconciliationWorker {
+    state: Data<State>,
+    is_running: bool,
+}
+
+impl WebhookReconciliationWorker {
+    pub fn new(state: Data<State>) -> Self {
+        Self { state, is_running: false }
+    }
+
+    fn reconcile(&mut self, ctx: &mut Context<Self>, only_missing_secrets: bool) {
+        // Previous reconciliation is still running
+        if self.is_running {
+            return;
+        }
+        self.is_running = true;
+
+        let state = self.state.clone();
+        let future = async move {
+            let result = match WebhookReconciliationService::new(&state) {
+                Ok(webhook_reconciliation_service) => match only_missing_secrets {
+                    true => webhook_reconciliation_service.reconcile_missing_secrets().await,
+                    false => webhook_reconciliation_service.reconcile(false).await,
+                },
+                Err(e) => Err(e),
+            };
+
+            match result {
+                Ok(reports) => {
+                    for report in reports.iter().filter(|report| report.has_changes()) {
+                        info!("Reconciled webhooks: {:?}", report);
+                    }
+                },
+                Err(e) => error!("Failed to reconcile webhooks: {}", e),
+            }
+        };
+
+        ctx.spawn(
+            future
+                .into_actor(self)
+                .map(|_, worker, _ctx| worker.is_running = false),
+        );
+    }
+}
+
+impl Actor for WebhookReconciliationWorker {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        self.reconcile(ctx, true);
+        ctx.run_interval(MISSING_SECRETS_INTERVAL, |worker, ctx| worker.reconcile(ctx, true));
+        ctx.run_interval(RECONCILIATION_INTERVAL, |worker, ctx| worker.reconcile(ctx, false));
+    }
+}
@@ -0,0 +1,59 @@
+use hmac::{Hmac, Mac, NewMac};
+use rand::{distributions::Alphanumeric, thread_rng, Rng};
+use sha2::Sha256;
+
+const SIGNATURE_PREFIX: &str = "sha256=";
+const SECRET_LENGTH: usize = 40;
+
+pub fn generate_secret() -> String {
+    thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(SECRET_LENGTH)
+        .collect()
+}
+
+// Checks X-Hub-Signature-256 header value against HMAC-SHA256 of the payload in constant time
+pub fn verify_signature(secret: &str, payload: &[u8], signature: &str) -> bool {
+    if secret.is_empty() || !signature.starts_with(SIGNATURE_PREFIX) {
+        return false;
+    }
+
+    let signature = match hex::decode(&signature[SIGNATURE_PREFIX.len()..]) {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+
+    let mut mac = Hmac::<Sha256>::new_varkey(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(payload);
+    mac.verify(&signature).is_ok()
+}
+
+#[test]
+fn test_verify_signature() {
+    // Example from https://docs.github.com/en/webhooks/using-webhooks/validating-webhook-deliveries
+    let signature = "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17";
+
+    assert!(verify_signature("It's a Secret to Everybody", b"Hello, World!", signature));
+}
+
+#[test]
+fn test_verify_signature_mismatch() {
+    let signature = "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17";
+
+    assert!(!verify_signature("wrong secret", b"Hello, World!", signature));
+    assert!(!verify_signature("It's a Secret to Everybody", b"Hello, World?", signature));
+}
+
+#[test]
+fn test_verify_signature_malformed_header() {
+    let secret = "It's a Secret to Everybody";
+
+    assert!(!verify_signature(secret, b"Hello, World!", ""));
+    assert!(!verify_signature(secret, b"Hello, World!", "sha1=757107ea0eb2509fc211221cce984b8a37570b6d"));
+    assert!(!verify_signature(secret, b"Hello, World!", "sha256=not-a-hex-string"));
+}
+
+#[test]
+fn test_verify_signature_empty_secret() {
+    assert!(!verify_signature("", b"Hello, World!", "sha256=00"));
+}
@@ -1,10 +1,21 @@
 use std::env;
+use std::sync::Arc;
+use std::time::Duration;
 
 use dotenv::dotenv;
 use diesel::r2d2::{ConnectionManager, PooledConnection};
 use diesel::PgConnection;
+use reqwest::{Client, Proxy};
 
 use crate::errors::GITrelloError;
+use crate::services::github_api_cache::GithubAPICache;
+use crate::services::github_api_client::{GitHubAPIClientFactory, HttpGitHubAPIClientFactory};
+use crate::services::gitrello_api_client::{GITrelloAPIClientFactory, HttpGITrelloAPIClientFactory};
+
+const DEFAULT_GITHUB_API_URL: &str = "https://api.github.com";
+const DEFAULT_HTTP_CONNECT_TIMEOUT_SECONDS: u64 = 5;
+const DEFAULT_HTTP_TIMEOUT_SECONDS: u64 = 30;
+const DEFAULT_HTTP_POOL_MAX_IDLE_PER_HOST: usize = 16;
 
 type DbPool = r2d2::Pool<ConnectionManager<PgConnection>>;
 pub type DbConnection = PooledConnection<ConnectionManager<PgConnection>>;
@@ -25,6 +36,14 @@ pub struct State {
     pub gitrello_url: String,
     pub gitrello_access_token: String,
     pub webhook_url: String,
+    pub admin_token: String,
+    pub github_api_url: String,
+    // Hosts profiles may point their GitHub API URL at, requests to any other host are refused
+    pub github_enterprise_hosts: Vec<String>,
+    pub github_api_cache: GithubAPICache,
+    pub http_client: Client,
+    pub github_api_client_factory: Arc<dyn GitHubAPIClientFactory>,
+    pub gitrello_api_client_factory: Arc<dyn GITrelloAPIClientFactory>,
 }
 
 impl State {
@@ -76,6 +95,47 @@ pub fn get_state() -> State {
         gitrello_url: env::var("GITRELLO_URL").expect("GITRELLO_URL"),
         gitrello_access_token: env::var("GITRELLO_ACCESS_TOKEN").expect("GITRELLO_ACCESS_TOKEN"),
         webhook_url: env::var("WEBHOOK_URL").expect("WEBHOOK_URL"),
+        admin_token: env::var("ADMIN_TOKEN").expect("ADMIN_TOKEN"),
+        github_api_url: env::var("GITHUB_API_URL")
+            .unwrap_or_else(|_| String::from(DEFAULT_GITHUB_API_URL))
+            .trim_end_matches('/')
+            .to_string(),
+        github_enterprise_hosts: env::var("GITHUB_ENTERPRISE_HOSTS")
+            .unwrap_or_default()
+            .split(',')
+            .map(|host| host.trim().to_lowercase())
+            .filter(|host| !host.is_empty())
+            .collect(),
+        github_api_cache: GithubAPICache::default(),
+        http_client: get_http_client(),
+        github_api_client_factory: Arc::new(HttpGitHubAPIClientFactory),
+        gitrello_api_client_factory: Arc::new(HttpGITrelloAPIClientFactory),
     };
     state
 }
+
+fn get_env_or_default<T: std::str::FromStr>(name: &str, default: T) -> T {
+    match env::var(name) {
+        Ok(value) => value.parse().unwrap_or_else(|_| panic!("{} is not a valid value for {}", value, name)),
+        Err(_) => default,
+    }
+}
+
+// One client for all outbound calls, so connections and TLS sessions are reused
+fn get_http_client() -> Client {
+    let connect_timeout = get_env_or_default("HTTP_CONNECT_TIMEOUT_SECONDS", DEFAULT_HTTP_CONNECT_TIMEOUT_SECONDS);
+    let timeout = get_env_or_default("HTTP_TIMEOUT_SECONDS", DEFAULT_HTTP_TIMEOUT_SECONDS);
+    let pool_max_idle_per_host = get_env_or_default("HTTP_POOL_MAX_IDLE_PER_HOST", DEFAULT_HTTP_POOL_MAX_IDLE_PER_HOST);
+
+    let mut builder = Client::builder()
+        .connect_timeout(Duration::from_secs(connect_timeout))
+        .timeout(Duration::from_secs(timeout))
+        .pool_max_idle_per_host(pool_max_idle_per_host);
+
+    if let Ok(proxy_url) = env::var("HTTP_PROXY_URL") {
+        let proxy = Proxy::all(proxy_url.as_str()).expect("HTTP_PROXY_URL is not a valid proxy URL");
+        builder = builder.proxy(proxy);
+    }
+
+    builder.build().expect("Failed to create HTTP client.")
+}
@@ -1,5 +1,8 @@
+use serde::de::IgnoredAny;
 use serde::{Deserialize, Serialize};
 
+use crate::value_objects::request_data::utils::null_as_default;
+
 #[derive(Deserialize, Debug)]
 pub struct APIError {
     pub message: String,
@@ -10,12 +13,26 @@ pub struct APIError {
 pub struct GithubUser {
     pub id: i64,
     pub login: String,
+    #[serde(rename = "type", default, deserialize_with = "null_as_default")]
+    pub user_type: String,
+}
+
+impl GithubUser {
+    pub fn is_bot(&self) -> bool {
+        self.user_type == "Bot"
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Owner {
+    pub login: String,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct Repository {
     pub id: i64,
     pub full_name: String,
+    pub default_branch: Option<String>,
 }
 
 impl Repository {
@@ -28,6 +45,29 @@ impl Repository {
 #[derive(Deserialize, Debug)]
 pub struct Webhook {
     pub id: i64,
+    #[serde(default)]
+    pub config: WebhookConfig,
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct WebhookConfig {
+    pub url: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CreateIssue {
+    pub title: String,
+    pub body: String,
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct UpdateIssue {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
 }
 
 #[derive(Serialize, Debug)]
@@ -36,16 +76,65 @@ pub struct CreateWebhook {
     pub events: Vec<String>,
 }
 
+#[derive(Serialize, Debug)]
+pub struct UpdateWebhook {
+    pub events: Vec<String>,
+}
+
 #[derive(Serialize, Debug)]
 pub struct CreateWebhookConfig {
     pub url: String,
     pub content_type: String,
+    pub secret: String,
 }
 
 
+#[derive(Deserialize, Debug)]
+pub struct Label {
+    pub name: String,
+}
+
 #[derive(Deserialize, Debug)]
 pub struct Issue {
+    pub id: i64,
+    pub number: i32,
     pub html_url: String,
     pub title: String,
+    pub body: Option<String>,
+    pub user: GithubUser,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub labels: Vec<Label>,
+    // Issues API lists pull requests as well, they are the ones with this key
+    #[serde(default)]
+    pub pull_request: Option<IgnoredAny>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Comment {
+    pub id: i64,
+    pub html_url: String,
+    #[serde(default, deserialize_with = "null_as_default")]
     pub body: String,
+    pub user: GithubUser,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PullRequest {
+    pub id: i64,
+    pub number: i32,
+    pub html_url: String,
+    pub title: String,
+    pub body: Option<String>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub draft: bool,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub merged: bool,
+    pub user: GithubUser,
+    pub merged_by: Option<GithubUser>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Commit {
+    pub id: String,
+    pub message: String,
 }
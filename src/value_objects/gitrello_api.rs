@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::value_objects::request_data::utils::i64_from_str;
+
 #[derive(Deserialize, Debug)]
 pub struct APIError {
     pub error_message: String,
@@ -25,3 +27,32 @@ pub struct CreateTicketRequest {
     pub title: String,
     pub body: String,
 }
+
+#[derive(Serialize, Debug, Default)]
+pub struct UpdateTicketRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_closed: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_archived: Option<bool>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Ticket {
+    #[serde(deserialize_with = "i64_from_str")]
+    pub id: i64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TicketCommentRequest {
+    pub body: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TicketComment {
+    #[serde(deserialize_with = "i64_from_str")]
+    pub id: i64,
+}
@@ -0,0 +1,21 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct GetTicketOutboxQueryParams {
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReconcileWebhooksQueryParams {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReplayWebhookDeliveriesRequest {
+    pub repository_owner: String,
+    pub repository_name: String,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+}
@@ -8,9 +8,34 @@ pub struct NewBoardRepositoryRequest {
     pub board_id: i64,
     pub repository_name: String,
     pub repository_owner: String,
+    #[serde(default)]
+    pub import_issues: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddBoardRepositoryRequest {
+    pub repository_name: String,
+    pub repository_owner: String,
+    #[serde(default)]
+    pub import_issues: bool,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct GetBoardRepositoryQueryParams {
     pub board_id: i64,
 }
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateBoardRepositoryRequest {
+    pub sync_pull_requests: Option<bool>,
+    pub allowed_bot_logins: Option<Vec<String>>,
+    pub events: Option<Vec<String>>,
+    pub ticket_title_template: Option<String>,
+    pub ticket_body_template: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TicketTemplatePreviewRequest {
+    pub ticket_title_template: Option<String>,
+    pub ticket_body_template: Option<String>,
+}
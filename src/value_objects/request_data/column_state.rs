@@ -0,0 +1,10 @@
+use serde::Deserialize;
+
+use crate::value_objects::request_data::utils::i64_from_str;
+
+#[derive(Debug, Deserialize)]
+pub struct ColumnStateRequest {
+    #[serde(deserialize_with = "i64_from_str")]
+    pub column_id: i64,
+    pub state: String,
+}
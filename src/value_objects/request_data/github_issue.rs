@@ -0,0 +1,12 @@
+use serde::Deserialize;
+
+use crate::value_objects::request_data::utils::i64_from_str;
+
+#[derive(Debug, Deserialize)]
+pub struct NewGithubIssueRequest {
+    #[serde(deserialize_with = "i64_from_str")]
+    pub ticket_id: i64,
+    pub title: String,
+    #[serde(default)]
+    pub body: String,
+}
@@ -3,4 +3,7 @@ use serde::Deserialize;
 #[derive(Clone, Debug, Deserialize)]
 pub struct NewGithubProfileRequest {
     pub access_token: String,
+    // GitHub Enterprise Server API, e.g. https://github.example.com/api/v3
+    #[serde(default)]
+    pub github_api_url: Option<String>,
 }
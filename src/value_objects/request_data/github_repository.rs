@@ -0,0 +1,11 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct GetGithubRepositoriesQueryParams {
+    // Comma separated, e.g. owner,collaborator
+    pub affiliation: Option<String>,
+    pub visibility: Option<String>,
+    pub search: Option<String>,
+    pub page: Option<usize>,
+    pub per_page: Option<usize>,
+}
@@ -0,0 +1,7 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct IssueFilterRequest {
+    pub kind: String,
+    pub value: String,
+}
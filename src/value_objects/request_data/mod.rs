@@ -1,4 +1,10 @@
+pub mod admin;
 pub mod board_repository;
+pub mod column_state;
+pub mod github_issue;
 pub mod github_profile;
+pub mod github_repository;
+pub mod issue_filter;
+pub mod ticket_update;
 pub mod utils;
 pub mod webhook;
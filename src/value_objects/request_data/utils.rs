@@ -24,3 +24,32 @@ pub fn i64_from_str<'de, D>(deserializer: D) -> Result<i64, D::Error>
         }
     };
 }
+
+pub fn optional_i64_from_str<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+    where D: Deserializer<'de>
+{
+    let value = Value::deserialize(deserializer)?;
+    return match value {
+        Value::Null => Ok(None),
+        Value::String(str_value) => {
+            i64::from_str(str_value.as_str()).map(Some).map_err(Error::custom)
+        },
+        Value::Number(number_value) => {
+            let i64_value = number_value.as_i64();
+            match i64_value {
+                Some(i64_value) => Ok(Some(i64_value)),
+                None => Err(Error::custom("value is not a valid i64"))
+            }
+        }
+        _ => {
+            Err(Error::custom("value is not a valid i64"))
+        }
+    };
+}
+
+// GitHub sends null rather than leaving some fields out, those are read as their default values
+pub fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where D: Deserializer<'de>, T: Deserialize<'de> + Default
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
@@ -1,10 +1,228 @@
 use serde::{Deserialize};
 
-use crate::value_objects::github_api::{Issue, Repository};
+use crate::value_objects::github_api::{
+    Comment, Commit, GithubUser, Issue, Owner, PullRequest, Repository,
+};
+use crate::value_objects::request_data::utils::null_as_default;
 
 #[derive(Deserialize)]
 pub struct IssueWebhookRequest {
     pub action: String,
     pub issue: Issue,
     pub repository: Repository,
+    pub sender: GithubUser,
+}
+
+#[derive(Deserialize)]
+pub struct IssueCommentWebhookRequest {
+    pub action: String,
+    pub issue: Issue,
+    pub comment: Comment,
+    pub repository: Repository,
+    pub sender: GithubUser,
+}
+
+#[derive(Deserialize)]
+pub struct PullRequestWebhookRequest {
+    pub action: String,
+    pub pull_request: PullRequest,
+    pub repository: Repository,
+    pub sender: GithubUser,
+}
+
+#[derive(Deserialize)]
+pub struct PushWebhookRequest {
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    pub commits: Vec<Commit>,
+    pub repository: Repository,
+    pub sender: GithubUser,
+}
+
+#[derive(Deserialize)]
+pub struct PreviousValue {
+    pub from: String,
+}
+
+#[derive(Deserialize)]
+pub struct RepositoryNameChanges {
+    pub name: PreviousValue,
+}
+
+#[derive(Deserialize)]
+pub struct PreviousOwner {
+    pub user: Option<Owner>,
+    pub organization: Option<Owner>,
+}
+
+#[derive(Deserialize)]
+pub struct RepositoryOwnerChanges {
+    pub from: PreviousOwner,
+}
+
+#[derive(Deserialize)]
+pub struct RepositoryChanges {
+    pub repository: Option<RepositoryNameChanges>,
+    pub owner: Option<RepositoryOwnerChanges>,
+}
+
+#[derive(Deserialize)]
+pub struct RepositoryWebhookRequest {
+    pub action: String,
+    pub repository: Repository,
+    pub changes: Option<RepositoryChanges>,
+    pub sender: GithubUser,
+}
+
+#[derive(Deserialize)]
+pub struct PingHook {
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+// Hooks of organizations are pinged without a repository
+#[derive(Deserialize)]
+pub struct PingWebhookRequest {
+    pub zen: String,
+    pub hook_id: i64,
+    pub hook: Option<PingHook>,
+    pub repository: Option<Repository>,
+}
+
+#[derive(Deserialize)]
+pub struct Installation {
+    pub id: i64,
+    pub account: Owner,
+}
+
+#[derive(Deserialize)]
+pub struct InstallationRepository {
+    pub full_name: String,
+}
+
+#[derive(Deserialize)]
+pub struct InstallationWebhookRequest {
+    pub action: String,
+    pub installation: Installation,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub repositories: Vec<InstallationRepository>,
+    pub sender: GithubUser,
+}
+
+#[derive(Deserialize)]
+pub struct WebhookEnvelope {
+    pub action: Option<String>,
+    pub repository: Option<Repository>,
+}
+
+#[cfg(test)]
+const ISSUES_OPENED: &str = include_str!("../../../fixtures/github_webhooks/issues_opened.json");
+#[cfg(test)]
+const ISSUE_COMMENT_CREATED: &str = include_str!("../../../fixtures/github_webhooks/issue_comment_created.json");
+#[cfg(test)]
+const PULL_REQUEST_CLOSED: &str = include_str!("../../../fixtures/github_webhooks/pull_request_closed.json");
+#[cfg(test)]
+const PUSH: &str = include_str!("../../../fixtures/github_webhooks/push.json");
+#[cfg(test)]
+const REPOSITORY_RENAMED: &str = include_str!("../../../fixtures/github_webhooks/repository_renamed.json");
+#[cfg(test)]
+const PING: &str = include_str!("../../../fixtures/github_webhooks/ping.json");
+#[cfg(test)]
+const INSTALLATION_CREATED: &str = include_str!("../../../fixtures/github_webhooks/installation_created.json");
+
+#[test]
+fn test_issue_webhook_request() {
+    let request = serde_json::from_str::<IssueWebhookRequest>(ISSUES_OPENED).unwrap();
+
+    assert_eq!(request.action, "opened");
+    assert_eq!(request.issue.number, 1347);
+    assert_eq!(request.issue.body, None);
+    assert_eq!(request.issue.html_url, "https://github.com/octocat/hello-world/issues/1347");
+    assert_eq!(request.issue.labels.iter().map(|label| label.name.as_str()).collect::<Vec<&str>>(), vec!["bug"]);
+    assert!(request.issue.pull_request.is_none());
+    assert_eq!(request.repository.split_full_name(), ("octocat", "hello-world"));
+    assert!(!request.sender.is_bot());
+}
+
+#[test]
+fn test_issue_webhook_request_with_null_labels() {
+    let payload = ISSUES_OPENED.replacen(r#""labels": ["#, r#""labels": null, "ignored": ["#, 1);
+    let request = serde_json::from_str::<IssueWebhookRequest>(payload.as_str()).unwrap();
+
+    assert!(request.issue.labels.is_empty());
+}
+
+#[test]
+fn test_issue_comment_webhook_request() {
+    let request = serde_json::from_str::<IssueCommentWebhookRequest>(ISSUE_COMMENT_CREATED).unwrap();
+
+    assert_eq!(request.action, "created");
+    assert_eq!(request.issue.body.as_deref(), Some("I'm having a problem with this."));
+    assert_eq!(request.comment.body, "Me too");
+    assert!(request.comment.user.is_bot());
+}
+
+#[test]
+fn test_pull_request_webhook_request() {
+    let request = serde_json::from_str::<PullRequestWebhookRequest>(PULL_REQUEST_CLOSED).unwrap();
+
+    assert_eq!(request.action, "closed");
+    assert!(request.pull_request.merged);
+    assert!(!request.pull_request.draft);
+    assert_eq!(request.pull_request.body, None);
+    assert_eq!(request.pull_request.merged_by.map(|user| user.login), Some(String::from("hubot")));
+}
+
+#[test]
+fn test_push_webhook_request() {
+    let request = serde_json::from_str::<PushWebhookRequest>(PUSH).unwrap();
+
+    assert_eq!(request.git_ref, "refs/heads/main");
+    assert_eq!(request.repository.default_branch.as_deref(), Some("main"));
+    assert_eq!(request.commits.len(), 1);
+    assert_eq!(request.commits[0].message, "Fix login button\n\nFixes #1347");
+}
+
+#[test]
+fn test_repository_webhook_request() {
+    let request = serde_json::from_str::<RepositoryWebhookRequest>(REPOSITORY_RENAMED).unwrap();
+    let changes = request.changes.unwrap();
+
+    assert_eq!(request.action, "renamed");
+    assert_eq!(request.repository.full_name, "octo-org/hello-world-2");
+    assert_eq!(changes.repository.unwrap().name.from, "hello-world");
+    assert_eq!(changes.owner.unwrap().from.user.map(|user| user.login), Some(String::from("octocat")));
+}
+
+#[test]
+fn test_ping_webhook_request() {
+    let request = serde_json::from_str::<PingWebhookRequest>(PING).unwrap();
+
+    assert_eq!(request.hook_id, 109948940);
+    assert_eq!(request.hook.map(|hook| hook.events.len()), Some(5));
+    assert!(request.repository.is_none());
+}
+
+#[test]
+fn test_installation_webhook_request() {
+    let request = serde_json::from_str::<InstallationWebhookRequest>(INSTALLATION_CREATED).unwrap();
+
+    assert_eq!(request.action, "created");
+    assert_eq!(request.installation.account.login, "octo-org");
+    assert_eq!(
+        request.repositories.iter().map(|repository| repository.full_name.as_str()).collect::<Vec<&str>>(),
+        vec!["octo-org/hello-world"],
+    );
+}
+
+#[test]
+fn test_webhook_envelope() {
+    for payload in [ISSUES_OPENED, ISSUE_COMMENT_CREATED, PULL_REQUEST_CLOSED, PUSH, REPOSITORY_RENAMED].iter() {
+        let envelope = serde_json::from_str::<WebhookEnvelope>(payload).unwrap();
+        assert!(envelope.repository.is_some());
+    }
+
+    let envelope = serde_json::from_str::<WebhookEnvelope>(PING).unwrap();
+    assert!(envelope.action.is_none());
+    assert!(envelope.repository.is_none());
 }
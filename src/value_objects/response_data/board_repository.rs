@@ -6,4 +6,16 @@ pub struct BoardRepositoryResponse {
     pub board_id: String,
     pub repository_name: String,
     pub repository_owner: String,
+    pub sync_pull_requests: bool,
+    pub allowed_bot_logins: Vec<String>,
+    pub is_read_only: bool,
+    pub events: Vec<String>,
+    pub ticket_title_template: Option<String>,
+    pub ticket_body_template: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TicketTemplatePreviewResponse {
+    pub title: String,
+    pub body: String,
 }
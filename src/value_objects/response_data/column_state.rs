@@ -0,0 +1,7 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct ColumnStateResponse {
+    pub column_id: String,
+    pub state: String,
+}
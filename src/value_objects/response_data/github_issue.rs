@@ -0,0 +1,10 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct GithubIssueResponse {
+    pub board_repository_id: i32,
+    pub ticket_id: String,
+    pub issue_id: String,
+    pub issue_number: i32,
+    pub html_url: String,
+}
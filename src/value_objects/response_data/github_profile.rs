@@ -6,4 +6,5 @@ pub struct GithubProfileResponse {
     pub user_id: String,
     pub github_user_id: String,
     pub github_login: String,
+    pub github_api_url: Option<String>,
 }
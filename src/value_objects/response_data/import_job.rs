@@ -0,0 +1,15 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct ImportJobResponse {
+    pub id: i32,
+    pub board_repository_id: i32,
+    pub status: String,
+    pub total: i32,
+    pub imported: i32,
+    pub skipped: i32,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,9 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct IssueFilterResponse {
+    pub id: i32,
+    pub board_repository_id: i32,
+    pub kind: String,
+    pub value: String,
+}
@@ -1,3 +1,11 @@
 pub mod board_repository;
+pub mod column_state;
+pub mod github_issue;
 pub mod github_profile;
 pub mod github_repository;
+pub mod import_job;
+pub mod issue_filter;
+pub mod ticket_outbox;
+pub mod webhook_delivery;
+pub mod webhook_health;
+pub mod webhook_reconciliation;
@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct WebhookDeliveryResponse {
+    pub id: i32,
+    pub delivery_id: String,
+    pub event: String,
+    pub action: Option<String>,
+    pub repository_owner: Option<String>,
+    pub repository_name: Option<String>,
+    pub received_at: DateTime<Utc>,
+    pub status: String,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookDeliveryReplayResponse {
+    pub id: i32,
+    pub delivery_id: String,
+    pub event: String,
+    pub status: String,
+    pub error: Option<String>,
+}
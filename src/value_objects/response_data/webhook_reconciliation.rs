@@ -0,0 +1,13 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct WebhookReconciliationResponse {
+    pub repository_owner: String,
+    pub repository_name: String,
+    pub missing_webhook: bool,
+    pub outdated_events: bool,
+    pub missing_secret: bool,
+    pub mismatched_github_webhook_ids: Vec<i32>,
+    pub orphaned_webhook_ids: Vec<String>,
+    pub error: Option<String>,
+}